@@ -5,6 +5,7 @@
 
 use crossbeam_queue::ArrayQueue;
 use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// Maximum GOOSE packet size (including Ethernet header)
@@ -75,12 +76,20 @@ impl Drop for PooledBuffer {
     }
 }
 
+/// Running pool-pressure counters shared across every `BufferPool` clone.
+#[derive(Default)]
+struct BufferPoolStats {
+    peak_in_use: AtomicUsize,
+    exhaustion_events: AtomicUsize,
+}
+
 /// Lock-free buffer pool for packet reception
-/// 
+///
 /// Uses crossbeam's lock-free ArrayQueue for allocation/deallocation
 #[derive(Clone)]
 pub struct BufferPool {
     queue: Arc<ArrayQueue<Vec<u8>>>,
+    stats: Arc<BufferPoolStats>,
 }
 
 impl BufferPool {
@@ -111,17 +120,32 @@ impl BufferPool {
             let _ = queue.push(buffer);
         }
 
-        Self { queue }
+        Self {
+            queue,
+            stats: Arc::new(BufferPoolStats::default()),
+        }
     }
 
     /// Get a buffer from the pool
-    /// 
-    /// Returns None if pool is exhausted (shouldn't happen in normal operation)
+    ///
+    /// Returns `None` if the pool is exhausted (shouldn't happen in normal
+    /// operation); each such miss is counted in [`Self::exhaustion_events`].
     pub fn acquire(&self) -> Option<PooledBuffer> {
-        self.queue.pop().map(|buffer| PooledBuffer {
-            buffer,
-            pool: Arc::clone(&self.queue),
-        })
+        match self.queue.pop() {
+            Some(buffer) => {
+                self.stats
+                    .peak_in_use
+                    .fetch_max(self.in_use(), Ordering::Relaxed);
+                Some(PooledBuffer {
+                    buffer,
+                    pool: Arc::clone(&self.queue),
+                })
+            }
+            None => {
+                self.stats.exhaustion_events.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
     }
 
     /// Get pool statistics
@@ -138,6 +162,22 @@ impl BufferPool {
     pub fn capacity(&self) -> usize {
         self.queue.capacity()
     }
+
+    /// Buffers currently leased out (not sitting in the free queue).
+    pub fn in_use(&self) -> usize {
+        self.queue.capacity() - self.queue.len()
+    }
+
+    /// High-water mark of [`Self::in_use`] observed so far.
+    pub fn peak_in_use(&self) -> usize {
+        self.stats.peak_in_use.load(Ordering::Relaxed)
+    }
+
+    /// Number of times `acquire()` has returned `None` because the pool was
+    /// exhausted.
+    pub fn exhaustion_events(&self) -> usize {
+        self.stats.exhaustion_events.load(Ordering::Relaxed)
+    }
 }
 
 #[cfg(test)]
@@ -171,6 +211,35 @@ mod tests {
         assert!(buf3.is_none(), "Pool should be exhausted");
     }
 
+    #[test]
+    fn test_buffer_pool_tracks_in_use_and_peak() {
+        let pool = BufferPool::new(4);
+        assert_eq!(pool.in_use(), 0);
+        assert_eq!(pool.peak_in_use(), 0);
+
+        let buf1 = pool.acquire().unwrap();
+        let buf2 = pool.acquire().unwrap();
+        assert_eq!(pool.in_use(), 2);
+        assert_eq!(pool.peak_in_use(), 2);
+
+        drop(buf1);
+        assert_eq!(pool.in_use(), 1);
+        assert_eq!(pool.peak_in_use(), 2, "peak should not decay");
+
+        drop(buf2);
+    }
+
+    #[test]
+    fn test_buffer_pool_counts_exhaustion_events() {
+        let pool = BufferPool::new(1);
+        let _held = pool.acquire().unwrap();
+        assert_eq!(pool.exhaustion_events(), 0);
+
+        assert!(pool.acquire().is_none());
+        assert!(pool.acquire().is_none());
+        assert_eq!(pool.exhaustion_events(), 2);
+    }
+
     #[test]
     fn test_pooled_buffer_operations() {
         let pool = BufferPool::new(10);
@@ -0,0 +1,87 @@
+//! Runtime control and statistics channel for a GOOSE send thread, modeled on
+//! crosvm's balloon stats/control channel: an operator can retune a running
+//! simulator's publish cadence, pause/resume it, or force a state change
+//! without a restart, and poll [`SimStats`] for throughput and buffer-pool
+//! pressure.
+
+use crate::goose::buffer_pool::BufferPool;
+use std::time::Duration;
+
+/// Commands a send thread (e.g. [`crate::goose::handle_send::handle_send`])
+/// accepts over its control channel.
+#[derive(Debug, Clone)]
+pub enum SimControl {
+    /// Ask the thread to push its current [`SimStats`] onto its reply channel.
+    RequestStats,
+    /// Change the interval between publishes.
+    SetSendInterval(Duration),
+    /// Stop publishing until a `Resume`.
+    Pause,
+    /// Resume publishing after a `Pause`.
+    Resume,
+    /// Force the next publish to advance `stNum` (a state change) instead of
+    /// just `sqNum` (a retransmission).
+    InjectStateChange,
+}
+
+/// Point-in-time snapshot returned by a send thread in response to
+/// [`SimControl::RequestStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SimStats {
+    pub frames_sent: u64,
+    pub st_num: u32,
+    pub sq_num: u32,
+    pub send_errors: u64,
+    pub buffers_in_use: usize,
+    pub peak_buffers_in_use: usize,
+    pub pool_exhaustion_events: usize,
+}
+
+impl SimStats {
+    /// Fill in the buffer-pool pressure fields from a live `pool`, leaving
+    /// the send-thread-owned counters (`frames_sent`, `st_num`, ...) as
+    /// already set on `self`.
+    pub fn with_pool_metrics(mut self, pool: &BufferPool) -> Self {
+        self.buffers_in_use = pool.in_use();
+        self.peak_buffers_in_use = pool.peak_in_use();
+        self.pool_exhaustion_events = pool.exhaustion_events();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_pool_metrics_merges_without_touching_send_counters() {
+        let pool = BufferPool::new(4);
+        let _held = pool.acquire().unwrap();
+        let _also_held = pool.acquire(); // still succeeds (3 left)
+
+        let stats = SimStats {
+            frames_sent: 42,
+            st_num: 7,
+            sq_num: 3,
+            send_errors: 1,
+            ..SimStats::default()
+        }
+        .with_pool_metrics(&pool);
+
+        assert_eq!(stats.frames_sent, 42);
+        assert_eq!(stats.st_num, 7);
+        assert_eq!(stats.buffers_in_use, 2);
+        assert_eq!(stats.peak_buffers_in_use, 2);
+        assert_eq!(stats.pool_exhaustion_events, 0);
+    }
+
+    #[test]
+    fn test_with_pool_metrics_reports_exhaustion() {
+        let pool = BufferPool::new(1);
+        let _held = pool.acquire().unwrap();
+        assert!(pool.acquire().is_none());
+
+        let stats = SimStats::default().with_pool_metrics(&pool);
+        assert_eq!(stats.pool_exhaustion_events, 1);
+    }
+}
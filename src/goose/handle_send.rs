@@ -1,14 +1,53 @@
-use crate::goose::types::{EthernetHeader, IECData, IECGoosePdu};
-use crate::goose::pdu::{encodeGooseFrame, getTimeMs};
+use crate::goose::buffer_pool::BufferPool;
+use crate::goose::control::{SimControl, SimStats};
+use crate::goose::nameplate_publisher::build_retransmit_frames;
+use crate::goose::types::{EthernetHeader, IECGoosePdu};
+use crate::os::linux_rt::pin_thread_to_core;
+use crate::pcs::publisher::RetransmissionProfile;
+use crate::pcs::NameplateConfig;
+use crate::threads::retransmit::{advance_and_encode, RetransmitFrame};
 use pnet_datalink::DataLinkSender;
 use libc::sched_getcpu;
 use std::thread;
-use crate::os::linux_rt::pin_thread_to_core;
-use log::{error, info};
+use std::time::{Duration, Instant};
+use crossbeam_channel::{Receiver, Sender};
+use log::{error, info, warn};
+
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Polling ceiling used on the first iteration, before any frame has a real
+/// `next_due` (mirrors `threads::retransmit::DEFAULT_POLL_MS`).
+const DEFAULT_POLL_MS: u64 = 1000;
 
-const GOOSE_BUFFER_SIZE: usize = 512;
+/// Built-in demo frame used when `configs` has no publishable nameplate rows,
+/// so a standalone invocation still has something to send.
+fn demo_frame() -> RetransmitFrame {
+    let header = EthernetHeader {
+        srcAddr: [0xe8, 0xd8, 0xd1, 0xeb, 0xcb, 0xb6],
+        dstAddr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x08],
+        TPID: [0x81, 0x00],
+        TCI: [0x80, 0x02],
+        ehterType: [0x88, 0xB8],
+        APPID: [0x00, 0x08],
+        length: [0x00, 0x00],
+    };
+    let pdu = IECGoosePdu {
+        gocbRef: "XD11LDevice1/LLN0$GO$Go_Gcb2".to_string(),
+        datSet: "XD11LDevice1/LLN0$dsGOOSE2".to_string(),
+        goID: "XD11LDevice1/LLN0.Go_Gcb2".to_string(),
+        confRev: 5,
+        ..IECGoosePdu::default()
+    };
+    RetransmitFrame::new(header, pdu, RetransmissionProfile::default())
+}
 
-pub fn handle_send(mut tx: Box<dyn DataLinkSender>, num_workers: usize) {
+pub fn handle_send(
+    mut tx: Box<dyn DataLinkSender>,
+    num_workers: usize,
+    control_rx: Receiver<SimControl>,
+    stats_tx: Sender<SimStats>,
+    buffer_pool: BufferPool,
+    configs: Vec<NameplateConfig>,
+) {
     thread::spawn(move || {
         // Pin main thread to the last core
         if let Err(e) = pin_thread_to_core(num_workers - 1) {
@@ -16,89 +55,93 @@ pub fn handle_send(mut tx: Box<dyn DataLinkSender>, num_workers: usize) {
         } else {
             info!("Send thread pinned to CPU: {}", unsafe { sched_getcpu() });
         }
-        let mut ether_header = EthernetHeader {
-            srcAddr: [0xe8, 0xd8, 0xd1, 0xeb, 0xcb, 0xb6],
-            dstAddr: [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x08],
-            TPID: [0x81, 0x00],
-            TCI: [0x80, 0x02],
-            ehterType: [0x88, 0xB8],
-            APPID: [0x00, 0x08],
-            length: [0x00, 0x00],
-        };
-        let current_time = getTimeMs();
-        let goose_data = vec![
-            IECData::float32(2.0),
-            IECData::float32(2.0),
-            IECData::float32(4.0),
-            IECData::float32(5.0),
-            IECData::float32(6.0),
-            IECData::float32(7.0),
-            IECData::float32(8.0),
-            IECData::float32(9.0),
-            IECData::float32(10.0),
-            IECData::float32(11.0),
-            IECData::float32(12.0),
-            IECData::float32(13.0),
-            IECData::float32(14.0),
-            IECData::float32(15.0),
-            IECData::float32(16.0),
-            IECData::float32(1.0),
-        ];
-        println!("goose data is:{:?}", goose_data);
-        let mut f1 = 0.1;
-        let mut f2 = 100.1;
 
-        let mut goose_pdu = IECGoosePdu {
-            gocbRef: "XD11LDevice1/LLN0$GO$Go_Gcb2".to_string(),
-            timeAllowedtoLive: 6400,
-            datSet: "XD11LDevice1/LLN0$dsGOOSE2".to_string(),
-            goID: "XD11LDevice1/LLN0.Go_Gcb2".to_string(),
-            t: current_time,
-            stNum: 12,
-            sqNum: 23,
-            simulation: false,
-            confRev: 5,
-            ndsCom: false,
-            numDatSetEntries: goose_data.len() as u32,
-            allData: goose_data,
-        };
+        let mut frames = build_retransmit_frames(&configs);
+        if frames.is_empty() {
+            warn!("Send thread: no valid nameplate rows, falling back to built-in demo frame");
+            frames.push(demo_frame());
+        }
 
-        goose_pdu.numDatSetEntries = goose_pdu.allData.len() as u32;
+        // Runtime-mutable state driven by `SimControl` (see chunk6-3): lets an
+        // operator query throughput/pool pressure, retune cadence, or force a
+        // state-change burst without restarting the simulator.
+        let mut paused = false;
+        let mut pending_state_change = false;
+        let mut frames_sent: u64 = 0;
+        let mut send_errors: u64 = 0;
 
         loop {
-            std::thread::sleep(std::time::Duration::from_millis(2));
-            f1 = f1 + 0.5;
-            f2 = f2 + 0.5;
+            while let Ok(command) = control_rx.try_recv() {
+                match command {
+                    SimControl::RequestStats => {
+                        let stats = SimStats {
+                            frames_sent,
+                            st_num: frames[0].pdu.stNum,
+                            sq_num: frames[0].pdu.sqNum,
+                            send_errors,
+                            ..SimStats::default()
+                        }
+                        .with_pool_metrics(&buffer_pool);
+                        if stats_tx.send(stats).is_err() {
+                            error!("Send thread: stats reply channel closed, dropping SimStats reply");
+                        }
+                    }
+                    SimControl::SetSendInterval(interval) => {
+                        // Retune every frame's stable-state (T0) retransmission
+                        // rate; the T1 burst-start interval is left alone since
+                        // it is a protocol property of the curve, not a cadence knob.
+                        let interval_ms = interval.as_millis().max(1) as u64;
+                        for frame in frames.iter_mut() {
+                            frame.profile.t0_ms = interval_ms;
+                            frame.profile.t_max_ms = interval_ms;
+                        }
+                    }
+                    SimControl::Pause => paused = true,
+                    SimControl::Resume => paused = false,
+                    SimControl::InjectStateChange => pending_state_change = true,
+                }
+            }
 
-            let goose_data = vec![
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f1),
-                IECData::float32(f2),
-            ];
+            if paused {
+                thread::sleep(PAUSED_POLL_INTERVAL);
+                continue;
+            }
 
-            goose_pdu.stNum = goose_pdu.stNum + 1;
-            goose_pdu.sqNum = 0;
-            goose_pdu.numDatSetEntries = goose_pdu.allData.len() as u32;
-            goose_pdu.allData = goose_data;
-            let mut buffer = [0 as u8; GOOSE_BUFFER_SIZE];
+            // Sleep until the earliest frame is due, same policy as
+            // `spawn_retransmit_thread`, just without the jitter compensator
+            // (this thread is not pinned to a dedicated sub-ms curve).
+            let wait_start = Instant::now();
+            let wait_ms = frames
+                .iter()
+                .map(|frame| frame.next_due().saturating_duration_since(wait_start).as_millis() as u64)
+                .min()
+                .unwrap_or(DEFAULT_POLL_MS)
+                .max(1);
+            thread::sleep(Duration::from_millis(wait_ms));
 
-            let goose_frame_size = encodeGooseFrame(&mut ether_header, &goose_pdu, &mut buffer, 0);
+            let now = Instant::now();
+            let reset_by_new_data = pending_state_change;
+            pending_state_change = false;
 
-            tx.send_to(&buffer[..goose_frame_size], None);
+            for frame in frames.iter_mut() {
+                if !(reset_by_new_data || now >= frame.next_due()) {
+                    continue;
+                }
+                match advance_and_encode(frame, reset_by_new_data, now, &buffer_pool) {
+                    Some(pooled) => match tx.send_to(&pooled, None) {
+                        Some(Ok(())) => frames_sent += 1,
+                        Some(Err(e)) => {
+                            send_errors += 1;
+                            error!("Send thread: failed to send GOOSE frame: {}", e);
+                        }
+                        None => {
+                            send_errors += 1;
+                            error!("Send thread: send_to declined to send the GOOSE frame");
+                        }
+                    },
+                    None => send_errors += 1,
+                }
+            }
         }
     });
 }
-
@@ -0,0 +1,369 @@
+//! GOOSE publisher subsystem driven by `NameplateConfig` rows (the CSV loaded
+//! by `load_nameplates_from_csv`), replacing the single hardcoded frame that
+//! used to live in `handle_send`. Each valid row becomes its own
+//! `RetransmitFrame`, so many independently-addressed PCS devices come from
+//! one CSV and are multiplexed through the shared `BufferPool`/retransmit
+//! pipeline instead of a per-row OS thread or a stack buffer.
+
+use crate::goose::types::{EthernetHeader, IECGoosePdu};
+use crate::pcs::nameplate::load_nameplates_from_csv;
+use crate::pcs::publisher::RetransmissionProfile;
+use crate::pcs::NameplateConfig;
+use crate::threads::publisher::MacAddr;
+use crate::threads::retransmit::RetransmitFrame;
+use anyhow::{Context, Result};
+use log::{info, warn};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Build one `(EthernetHeader, IECGoosePdu)` GOOSE frame from a nameplate
+/// row's `goose_*` fields. `allData` is left empty here; per-PCS-type
+/// dataset layout is owned by the mapping pipeline in `pcs::publisher`.
+fn build_goose_frame_from_nameplate(config: &NameplateConfig) -> Result<(EthernetHeader, IECGoosePdu)> {
+    let src_addr = config
+        .goose_src_addr
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Missing goose_srcAddr for row {:?}", config.row_number))?;
+    let src_addr = MacAddr::from_str(src_addr)
+        .with_context(|| format!("invalid goose_srcAddr for row {:?}", config.row_number))?;
+
+    let dst_addr = config
+        .goose_dst_addr
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Missing goose_dstAddr for row {:?}", config.row_number))?;
+    let dst_addr = MacAddr::from_str(dst_addr)
+        .with_context(|| format!("invalid goose_dstAddr for row {:?}", config.row_number))?;
+    dst_addr.check_goose_multicast(false)?;
+
+    let appid = config
+        .goose_appid
+        .ok_or_else(|| anyhow::anyhow!("Missing goose_appid for row {:?}", config.row_number))?;
+
+    let gocb_ref = config
+        .goose_gocb_ref
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Missing goose_gocbRef for row {:?}", config.row_number))?;
+    let data_set = config
+        .goose_data_set
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Missing goose_dataSet for row {:?}", config.row_number))?;
+    let go_id = config
+        .goose_go_id
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Missing goose_goID for row {:?}", config.row_number))?;
+
+    let simulation = config
+        .goose_simulation
+        .as_deref()
+        .map(|s| s.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    let conf_rev = config
+        .goose_conf_rev
+        .as_deref()
+        .map(|s| s.parse::<u32>())
+        .transpose()
+        .with_context(|| format!("invalid goose_confRev for row {:?}", config.row_number))?
+        .unwrap_or(1);
+    let nds_com = config
+        .goose_nds_com
+        .as_deref()
+        .map(|s| s.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    let header = EthernetHeader {
+        srcAddr: src_addr.0,
+        dstAddr: dst_addr.0,
+        ehterType: [0x88, 0xB8],
+        APPID: appid.to_be_bytes(),
+        ..EthernetHeader::default()
+    };
+
+    let pdu = IECGoosePdu {
+        gocbRef: gocb_ref.to_string(),
+        datSet: data_set.to_string(),
+        goID: go_id.to_string(),
+        simulation,
+        confRev: conf_rev,
+        ndsCom: nds_com,
+        ..IECGoosePdu::default()
+    };
+
+    Ok((header, pdu))
+}
+
+/// Build one `RetransmitFrame` (on the default retransmission curve) per
+/// valid nameplate row, skipping and logging rows missing required GOOSE
+/// fields rather than failing the whole batch.
+pub fn build_retransmit_frames(configs: &[NameplateConfig]) -> Vec<RetransmitFrame> {
+    configs
+        .iter()
+        .filter_map(|config| match build_goose_frame_from_nameplate(config) {
+            Ok((header, pdu)) => Some(RetransmitFrame::new(header, pdu, RetransmissionProfile::default())),
+            Err(e) => {
+                warn!("Skipping nameplate row {:?}: {}", config.row_number, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Re-read and re-validate `csv_path` (the same uniqueness/presence checks
+/// `load_nameplates_from_csv` already enforces: unique `goose_appid`/`logical_id`,
+/// non-empty `pcs_type`, `feed_line_id > 0`) and diff the resulting rows
+/// against the live `frames`, keyed by `IECGoosePdu::goID`: a goID not seen
+/// before starts a new publisher, a goID no longer present is dropped (its
+/// publisher stops), and a goID whose header/PDU fields changed has its live
+/// frame replaced - `confRev` bumped by one from its prior value per IEC
+/// 61850 configuration-revision semantics, mirroring
+/// `PublisherConfigStore::reload`'s policy for the JSON-config pipeline. A
+/// goID whose fields are unchanged is left completely untouched, so its
+/// `stNum`/`sqNum`/retransmission timing survive the reload.
+///
+/// Returns `Err` - leaving `frames` untouched - if the CSV can't be opened or
+/// every row fails validation, so a broken edit can't tear down a running set
+/// of publishers.
+pub fn reload_retransmit_frames<P: AsRef<Path>>(csv_path: P, frames: &mut Vec<RetransmitFrame>) -> Result<()> {
+    let configs = load_nameplates_from_csv(&csv_path)?;
+    let mut candidates = build_retransmit_frames(&configs);
+    if candidates.is_empty() {
+        anyhow::bail!(
+            "reloaded nameplate CSV '{:?}' produced no publishable rows, keeping previous configuration",
+            csv_path.as_ref()
+        );
+    }
+
+    let mut to_upsert: Vec<RetransmitFrame> = Vec::new();
+    let mut live_go_ids: Vec<String> = Vec::with_capacity(candidates.len());
+    for mut candidate in candidates.drain(..) {
+        live_go_ids.push(candidate.pdu.goID.clone());
+        match frames.iter().find(|frame| frame.pdu.goID == candidate.pdu.goID) {
+            Some(existing) => {
+                // confRev is deliberately excluded: it is the *output* of this
+                // diff (bumped below on a real change), not an input - comparing
+                // it here would make an already-bumped live frame look "changed"
+                // forever against the CSV's original confRev on every later poll.
+                let unchanged = existing.header.srcAddr == candidate.header.srcAddr
+                    && existing.header.dstAddr == candidate.header.dstAddr
+                    && existing.header.APPID == candidate.header.APPID
+                    && existing.pdu.gocbRef == candidate.pdu.gocbRef
+                    && existing.pdu.datSet == candidate.pdu.datSet
+                    && existing.pdu.simulation == candidate.pdu.simulation
+                    && existing.pdu.ndsCom == candidate.pdu.ndsCom;
+                if unchanged {
+                    continue; // leave the live frame (stNum/sqNum/timing included) alone
+                }
+                candidate.pdu.confRev = existing.pdu.confRev + 1;
+                to_upsert.push(candidate);
+            }
+            None => to_upsert.push(candidate),
+        }
+    }
+
+    frames.retain(|frame| live_go_ids.contains(&frame.pdu.goID));
+    for candidate in to_upsert {
+        match frames.iter_mut().find(|frame| frame.pdu.goID == candidate.pdu.goID) {
+            Some(slot) => *slot = candidate,
+            None => frames.push(candidate),
+        }
+    }
+
+    info!(
+        "Nameplate reload: {} publisher(s) now live",
+        frames.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> NameplateConfig {
+        NameplateConfig {
+            row_number: Some(1),
+            device_id: Some("PCS1".to_string()),
+            goose_appid: Some(0x0008),
+            goose_src_addr: Some("e8-d8-d1-eb-cb-b6".to_string()),
+            goose_dst_addr: Some("01-0C-CD-01-00-08".to_string()),
+            goose_tpid: None,
+            goose_tci: None,
+            goose_gocb_ref: Some("XD11LDevice1/LLN0$GO$Go_Gcb2".to_string()),
+            goose_data_set: Some("XD11LDevice1/LLN0$dsGOOSE2".to_string()),
+            goose_go_id: Some("XD11LDevice1/LLN0.Go_Gcb2".to_string()),
+            goose_simulation: Some("false".to_string()),
+            goose_conf_rev: Some("5".to_string()),
+            goose_nds_com: Some("false".to_string()),
+            feed_line_id: None,
+            feed_line_alias: None,
+            logical_id: Some(1),
+            pcs_type: Some("type_a".to_string()),
+            pms_appid: None,
+        }
+    }
+
+    #[test]
+    fn test_build_goose_frame_from_nameplate_populates_header_and_pdu() {
+        let config = valid_config();
+        let (header, pdu) = build_goose_frame_from_nameplate(&config).expect("should build");
+
+        assert_eq!(header.srcAddr, [0xe8, 0xd8, 0xd1, 0xeb, 0xcb, 0xb6]);
+        assert_eq!(header.dstAddr, [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x08]);
+        assert_eq!(header.APPID, 0x0008u16.to_be_bytes());
+        assert_eq!(pdu.gocbRef, "XD11LDevice1/LLN0$GO$Go_Gcb2");
+        assert_eq!(pdu.datSet, "XD11LDevice1/LLN0$dsGOOSE2");
+        assert_eq!(pdu.goID, "XD11LDevice1/LLN0.Go_Gcb2");
+        assert_eq!(pdu.confRev, 5);
+        assert!(!pdu.simulation);
+        assert!(!pdu.ndsCom);
+    }
+
+    #[test]
+    fn test_build_goose_frame_from_nameplate_rejects_missing_field() {
+        let mut config = valid_config();
+        config.goose_gocb_ref = None;
+
+        let err = build_goose_frame_from_nameplate(&config).unwrap_err();
+        assert!(err.to_string().contains("goose_gocbRef"));
+    }
+
+    #[test]
+    fn test_build_retransmit_frames_skips_invalid_rows_and_keeps_valid_ones() {
+        let mut missing_appid = valid_config();
+        missing_appid.row_number = Some(2);
+        missing_appid.goose_appid = None;
+
+        let configs = vec![valid_config(), missing_appid];
+        let frames = build_retransmit_frames(&configs);
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].pdu.goID, "XD11LDevice1/LLN0.Go_Gcb2");
+    }
+
+    // Unique per-test scratch file under the OS temp dir, mirroring
+    // `threads::publisher`'s `temp_cfg_path` helper for `PublisherConfigStore`.
+    fn temp_csv_path(test_name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        std::env::temp_dir().join(format!(
+            "nameplate_reload_{}_{}_{}.csv",
+            std::process::id(),
+            test_name,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    const CSV_HEADER: &str = "no,device_id,goose_appid,goose_srcAddr,goose_dstAddr,goose_TPID,goose_TCI,goose_gocbRef,goose_dataSet,goose_goID,goose_simulation,goose_confRev,goose_ndsCom,feed_line_id,feed_line_alias,logical_id,pcs_type,pms_appid";
+
+    fn write_csv(path: &std::path::Path, rows: &[&str]) {
+        let mut contents = String::from(CSV_HEADER);
+        contents.push('\n');
+        for row in rows {
+            contents.push_str(row);
+            contents.push('\n');
+        }
+        std::fs::write(path, contents).expect("write temp nameplate CSV");
+    }
+
+    fn row(no: u16, appid: u16, go_id: &str, logical_id: u16) -> String {
+        format!(
+            "{no},PCS{no},{appid},e8-d8-d1-eb-cb-b6,01-0C-CD-01-00-08,,,{go_id}$GO$Gcb,{go_id}$dsGOOSE,{go_id},false,5,false,,,{logical_id},type_a,",
+            no = no,
+            appid = appid,
+            go_id = go_id,
+            logical_id = logical_id,
+        )
+    }
+
+    #[test]
+    fn test_reload_retransmit_frames_adds_new_publisher() {
+        let path = temp_csv_path("adds_new");
+        write_csv(&path, &[&row(1, 1, "pubA", 1)]);
+
+        let mut frames = Vec::new();
+        reload_retransmit_frames(&path, &mut frames).expect("first reload loads pubA");
+        assert_eq!(frames.len(), 1);
+
+        write_csv(&path, &[&row(1, 1, "pubA", 1), &row(2, 2, "pubB", 2)]);
+        reload_retransmit_frames(&path, &mut frames).expect("second reload adds pubB");
+
+        assert_eq!(frames.len(), 2);
+        assert!(frames.iter().any(|f| f.pdu.goID == "pubB"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_reload_retransmit_frames_removes_dropped_publisher() {
+        let path = temp_csv_path("removes_dropped");
+        write_csv(&path, &[&row(1, 1, "pubA", 1), &row(2, 2, "pubB", 2)]);
+
+        let mut frames = Vec::new();
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+        assert_eq!(frames.len(), 2);
+
+        write_csv(&path, &[&row(1, 1, "pubA", 1)]);
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].pdu.goID, "pubA");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_reload_retransmit_frames_preserves_state_when_unchanged() {
+        let path = temp_csv_path("unchanged");
+        write_csv(&path, &[&row(1, 1, "pubA", 1)]);
+
+        let mut frames = Vec::new();
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+        frames[0].pdu.stNum = 42;
+        frames[0].pdu.sqNum = 7;
+
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+
+        assert_eq!(frames[0].pdu.stNum, 42, "unchanged row must not reset stNum");
+        assert_eq!(frames[0].pdu.sqNum, 7, "unchanged row must not reset sqNum");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_reload_retransmit_frames_bumps_conf_rev_on_change() {
+        let path = temp_csv_path("bumps_conf_rev");
+        write_csv(&path, &[&row(1, 1, "pubA", 1)]);
+
+        let mut frames = Vec::new();
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+        let original_conf_rev = frames[0].pdu.confRev;
+
+        // Same goID, but a different APPID - a real config change.
+        write_csv(&path, &[&row(1, 2, "pubA", 1)]);
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+
+        assert_eq!(frames[0].header.APPID, 2u16.to_be_bytes());
+        assert_eq!(frames[0].pdu.confRev, original_conf_rev + 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_reload_retransmit_frames_rejects_empty_result_keeping_previous() {
+        let path = temp_csv_path("rejects_empty");
+        write_csv(&path, &[&row(1, 1, "pubA", 1)]);
+
+        let mut frames = Vec::new();
+        reload_retransmit_frames(&path, &mut frames).unwrap();
+
+        // A row with appid 0 fails `load_nameplates_from_csv`'s own validation
+        // and is dropped, leaving zero publishable rows in the reloaded file.
+        write_csv(&path, &[&row(1, 0, "pubA", 1)]);
+        let result = reload_retransmit_frames(&path, &mut frames);
+
+        assert!(result.is_err());
+        assert_eq!(frames.len(), 1, "previous configuration must be retained");
+        assert_eq!(frames[0].pdu.goID, "pubA");
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
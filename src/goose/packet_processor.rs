@@ -3,7 +3,7 @@
 
 use crate::goose::buffer_pool::PooledBuffer;
 use crate::goose::pdu::decodeGooseFrame;
-use crate::pcs::types::SubscriberPCSData;
+use crate::pcs::types::{AppIdIndex, SubscriberPCSData};
 use std::sync::{Arc, Mutex};
 use log::{info, warn};
 
@@ -12,7 +12,13 @@ pub struct PacketData {
     pub data: PooledBuffer,
 }
 
-pub fn process_rx_packet(pcs_data_pool: Arc<Mutex<std::collections::HashMap<u16, SubscriberPCSData>>>, (lan_id, packet): (u16, PacketData)) {
+/// Decode a received frame and update only the PCS entries that subscribe to its
+/// APPID, via `appid_index`, instead of scanning every entry in `pcs_data_pool`.
+pub fn process_rx_packet(
+    pcs_data_pool: Arc<Mutex<std::collections::HashMap<u16, SubscriberPCSData>>>,
+    appid_index: Arc<AppIdIndex>,
+    (lan_id, packet): (u16, PacketData),
+) {
     // Replace with your actual processing logic
     let mut rx_header = Default::default();
     let mut rx_pdu = Default::default();
@@ -23,6 +29,12 @@ pub fn process_rx_packet(pcs_data_pool: Arc<Mutex<std::collections::HashMap<u16,
         info!("decode PDU {:?}", rx_pdu);
         info!("===============================");
         let appid = u16::from_be_bytes(rx_header.APPID);
+        let matched_pcs_ids = appid_index.pcs_ids_for_appid(appid);
+        if matched_pcs_ids.is_empty() {
+            warn!("No PCS subscribed to APPID 0x{:04X} on LAN{}", appid, lan_id);
+            return;
+        }
+
         let mut pcs_data_map = match pcs_data_pool.lock() {
             Ok(map) => map,
             Err(poisoned) => {
@@ -30,15 +42,11 @@ pub fn process_rx_packet(pcs_data_pool: Arc<Mutex<std::collections::HashMap<u16,
                 poisoned.into_inner()
             }
         };
-        for (_pcs_id, pcs) in pcs_data_map.iter_mut() {
-            if let Some(nameplate_appid) = pcs.nameplate_appid() {
-                if nameplate_appid == appid {
-                    pcs.update_from_goose(&rx_pdu, lan_id);
-                    info!("Matched PCS ID: {}, Updated with GOOSE data", pcs.pcs_id());
-                }
+        for pcs_id in matched_pcs_ids {
+            if let Some(pcs) = pcs_data_map.get_mut(&pcs_id) {
+                pcs.update_from_goose(&rx_pdu, lan_id);
+                info!("Matched PCS ID: {}, Updated with GOOSE data", pcs.pcs_id());
             }
-        }   
+        }
     }
-
-
 }
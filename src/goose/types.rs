@@ -1,9 +1,26 @@
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
+use anyhow::{bail, Result};
 use serde::{Serialize, Deserialize};
 use log::info;
 
-#[derive(Debug,Serialize, Deserialize,Clone)]
+// Context-specific BER tags for the IEC 61850-8-1 `Data` CHOICE, used to
+// encode/decode `allData` entries. The constructed bit (0x20) is set on
+// `array`/`structure`, whose content is the concatenated BER of their
+// children; every other variant is primitive.
+const BER_TAG_ARRAY: u8 = 0xA1;
+const BER_TAG_STRUCTURE: u8 = 0xA2;
+const BER_TAG_BOOLEAN: u8 = 0x83;
+const BER_TAG_BIT_STRING: u8 = 0x84;
+const BER_TAG_INTEGER: u8 = 0x85;
+const BER_TAG_UNSIGNED: u8 = 0x86;
+const BER_TAG_FLOATING_POINT: u8 = 0x87;
+const BER_TAG_OCTET_STRING: u8 = 0x89;
+const BER_TAG_VISIBLE_STRING: u8 = 0x8A;
+const BER_TAG_MMS_STRING: u8 = 0x90;
+const BER_TAG_UTC_TIME: u8 = 0x91;
+
+#[derive(Debug,Serialize, Deserialize,Clone,PartialEq)]
 pub enum IECData{
     array(Vec<IECData>),
     structure(Vec<IECData>),
@@ -260,6 +277,181 @@ impl IECData {
     }
 }
 
+/// Append a BER definite-form length to `buf`: short form (single byte) for
+/// `len < 128`, long form (`0x80 | length-of-length` followed by the
+/// big-endian length octets) otherwise.
+pub(crate) fn encode_ber_length(len: usize, buf: &mut Vec<u8>) {
+    if len < 128 {
+        buf.push(len as u8);
+    } else {
+        let len_bytes = len.to_be_bytes();
+        let significant = &len_bytes[len_bytes.iter().position(|b| *b != 0).unwrap_or(len_bytes.len() - 1)..];
+        buf.push(0x80 | significant.len() as u8);
+        buf.extend_from_slice(significant);
+    }
+}
+
+/// Decode a BER definite-form length from the start of `bytes`.
+/// Returns `(length, bytes_consumed_by_the_length_field)`.
+pub(crate) fn decode_ber_length(bytes: &[u8]) -> Result<(usize, usize)> {
+    let first = *bytes.first().ok_or_else(|| anyhow::anyhow!("Truncated BER length field"))?;
+    if first & 0x80 == 0 {
+        return Ok((first as usize, 1));
+    }
+    let num_octets = (first & 0x7F) as usize;
+    if num_octets == 0 {
+        bail!("BER indefinite-length form is not supported");
+    }
+    if bytes.len() < 1 + num_octets {
+        bail!("Truncated BER long-form length field");
+    }
+    let mut len: usize = 0;
+    for &b in &bytes[1..1 + num_octets] {
+        len = (len << 8) | b as usize;
+    }
+    Ok((len, 1 + num_octets))
+}
+
+impl IECData {
+    /// Encode this value's IEC 61850 MMS `Data` representation (as used
+    /// inside GOOSE `allData`) in tag-length-value BER form, appending it to
+    /// `buf`.
+    pub fn encode_ber(&self, buf: &mut Vec<u8>) {
+        match self {
+            IECData::array(items) | IECData::structure(items) => {
+                let tag = if self.is_array() { BER_TAG_ARRAY } else { BER_TAG_STRUCTURE };
+                let mut content = Vec::new();
+                for item in items {
+                    item.encode_ber(&mut content);
+                }
+                buf.push(tag);
+                encode_ber_length(content.len(), buf);
+                buf.extend_from_slice(&content);
+            }
+            IECData::boolean(b) => {
+                buf.push(BER_TAG_BOOLEAN);
+                encode_ber_length(1, buf);
+                buf.push(if *b { 0xFF } else { 0x00 });
+            }
+            IECData::int8(v) => encode_ber_primitive(BER_TAG_INTEGER, &v.to_be_bytes(), buf),
+            IECData::int16(v) => encode_ber_primitive(BER_TAG_INTEGER, &v.to_be_bytes(), buf),
+            IECData::int32(v) => encode_ber_primitive(BER_TAG_INTEGER, &v.to_be_bytes(), buf),
+            IECData::int64(v) => encode_ber_primitive(BER_TAG_INTEGER, &v.to_be_bytes(), buf),
+            IECData::int8u(v) => encode_ber_primitive(BER_TAG_UNSIGNED, &v.to_be_bytes(), buf),
+            IECData::int16u(v) => encode_ber_primitive(BER_TAG_UNSIGNED, &v.to_be_bytes(), buf),
+            IECData::int32u(v) => encode_ber_primitive(BER_TAG_UNSIGNED, &v.to_be_bytes(), buf),
+            IECData::float32(v) => {
+                let mut content = vec![8u8]; // exponent width in bits
+                content.extend_from_slice(&v.to_be_bytes());
+                encode_ber_primitive(BER_TAG_FLOATING_POINT, &content, buf);
+            }
+            IECData::float64(v) => {
+                let mut content = vec![11u8]; // exponent width in bits
+                content.extend_from_slice(&v.to_be_bytes());
+                encode_ber_primitive(BER_TAG_FLOATING_POINT, &content, buf);
+            }
+            IECData::visible_string(s) => encode_ber_primitive(BER_TAG_VISIBLE_STRING, s.as_bytes(), buf),
+            IECData::mms_string(s) => encode_ber_primitive(BER_TAG_MMS_STRING, s.as_bytes(), buf),
+            IECData::bit_string { padding, val } => {
+                let mut content = vec![*padding];
+                content.extend_from_slice(val);
+                encode_ber_primitive(BER_TAG_BIT_STRING, &content, buf);
+            }
+            IECData::octet_string(v) => encode_ber_primitive(BER_TAG_OCTET_STRING, v, buf),
+            IECData::utc_time(t) => encode_ber_primitive(BER_TAG_UTC_TIME, t, buf),
+        }
+    }
+
+    /// Decode one BER tag-length-value entry from the start of `bytes` into
+    /// an `IECData`. Returns `(value, bytes_consumed)`. Rejects truncated
+    /// length/content fields and, for constructed tags, requires the decoded
+    /// children to exactly consume the declared length.
+    pub fn decode_ber(bytes: &[u8]) -> Result<(IECData, usize)> {
+        let tag = *bytes.first().ok_or_else(|| anyhow::anyhow!("Empty BER input"))?;
+        let (len, len_size) = decode_ber_length(&bytes[1..])?;
+        let header_size = 1 + len_size;
+        if bytes.len() < header_size + len {
+            bail!("Truncated BER content: declared length {} but only {} bytes available", len, bytes.len() - header_size);
+        }
+        let content = &bytes[header_size..header_size + len];
+        let total_consumed = header_size + len;
+
+        let value = match tag {
+            BER_TAG_ARRAY | BER_TAG_STRUCTURE => {
+                let mut items = Vec::new();
+                let mut offset = 0;
+                while offset < content.len() {
+                    let (item, consumed) = IECData::decode_ber(&content[offset..])?;
+                    items.push(item);
+                    offset += consumed;
+                }
+                if offset != content.len() {
+                    bail!("Constructed BER value's children did not exactly consume its declared length");
+                }
+                if tag == BER_TAG_ARRAY {
+                    IECData::array(items)
+                } else {
+                    IECData::structure(items)
+                }
+            }
+            BER_TAG_BOOLEAN => {
+                let b = *content.first().ok_or_else(|| anyhow::anyhow!("Empty BOOLEAN content"))?;
+                IECData::boolean(b != 0)
+            }
+            BER_TAG_INTEGER => match content.len() {
+                1 => IECData::int8(i8::from_be_bytes(content.try_into().unwrap())),
+                2 => IECData::int16(i16::from_be_bytes(content.try_into().unwrap())),
+                4 => IECData::int32(i32::from_be_bytes(content.try_into().unwrap())),
+                8 => IECData::int64(i64::from_be_bytes(content.try_into().unwrap())),
+                n => bail!("Unsupported BER INTEGER width: {} bytes", n),
+            },
+            BER_TAG_UNSIGNED => match content.len() {
+                1 => IECData::int8u(u8::from_be_bytes(content.try_into().unwrap())),
+                2 => IECData::int16u(u16::from_be_bytes(content.try_into().unwrap())),
+                4 => IECData::int32u(u32::from_be_bytes(content.try_into().unwrap())),
+                n => bail!("Unsupported BER unsigned INTEGER width: {} bytes", n),
+            },
+            BER_TAG_FLOATING_POINT => {
+                let format_byte = *content.first().ok_or_else(|| anyhow::anyhow!("Empty FLOATING-POINT content"))?;
+                let mantissa = &content[1..];
+                match (format_byte, mantissa.len()) {
+                    (8, 4) => IECData::float32(f32::from_be_bytes(mantissa.try_into().unwrap())),
+                    (11, 8) => IECData::float64(f64::from_be_bytes(mantissa.try_into().unwrap())),
+                    (fmt, n) => bail!("Unsupported BER FLOATING-POINT format byte {} with {} mantissa bytes", fmt, n),
+                }
+            }
+            BER_TAG_VISIBLE_STRING => IECData::visible_string(
+                String::from_utf8(content.to_vec()).map_err(|e| anyhow::anyhow!("Invalid UTF-8 in VISIBLE STRING: {}", e))?,
+            ),
+            BER_TAG_MMS_STRING => IECData::mms_string(
+                String::from_utf8(content.to_vec()).map_err(|e| anyhow::anyhow!("Invalid UTF-8 in MMSString: {}", e))?,
+            ),
+            BER_TAG_BIT_STRING => {
+                let padding = *content.first().ok_or_else(|| anyhow::anyhow!("Empty BIT STRING content"))?;
+                IECData::bit_string {
+                    padding,
+                    val: content[1..].to_vec(),
+                }
+            }
+            BER_TAG_OCTET_STRING => IECData::octet_string(content.to_vec()),
+            BER_TAG_UTC_TIME => {
+                let t: [u8; 8] = content.try_into().map_err(|_| anyhow::anyhow!("UtcTime content must be 8 bytes, got {}", content.len()))?;
+                IECData::utc_time(t)
+            }
+            other => bail!("Unknown BER tag: 0x{:02X}", other),
+        };
+
+        Ok((value, total_consumed))
+    }
+}
+
+/// Append `tag`, a BER length for `content`, then `content` itself to `buf`.
+pub(crate) fn encode_ber_primitive(tag: u8, content: &[u8], buf: &mut Vec<u8>) {
+    buf.push(tag);
+    encode_ber_length(content.len(), buf);
+    buf.extend_from_slice(content);
+}
+
 #[derive(Debug,Default)]
 pub struct EthernetHeader {
     pub srcAddr:[u8;6],
@@ -293,3 +485,77 @@ impl IECGoosePdu {
     }
 }
 
+#[cfg(test)]
+mod ber_tests {
+    use super::*;
+
+    fn round_trip(value: IECData) {
+        let mut buf = Vec::new();
+        value.encode_ber(&mut buf);
+        let (decoded, consumed) = IECData::decode_ber(&buf).expect("decode_ber should succeed");
+        assert_eq!(consumed, buf.len());
+        assert_eq!(decoded.variant_name(), value.variant_name());
+    }
+
+    #[test]
+    fn test_round_trip_primitives() {
+        round_trip(IECData::boolean(true));
+        round_trip(IECData::int32(-12345));
+        round_trip(IECData::int32u(987_654));
+        round_trip(IECData::float32(3.25));
+        round_trip(IECData::float64(-1.5e10));
+        round_trip(IECData::visible_string("GOOSE1".to_string()));
+        round_trip(IECData::octet_string(vec![0xDE, 0xAD, 0xBE, 0xEF]));
+        round_trip(IECData::bit_string { padding: 3, val: vec![0b1010_0000] });
+        round_trip(IECData::utc_time([1, 2, 3, 4, 5, 6, 7, 8]));
+    }
+
+    #[test]
+    fn test_round_trip_nested_structure() {
+        let value = IECData::structure(vec![
+            IECData::boolean(false),
+            IECData::array(vec![IECData::int16(1), IECData::int16(2), IECData::int16(3)]),
+            IECData::visible_string("nested".to_string()),
+        ]);
+        round_trip(value);
+    }
+
+    #[test]
+    fn test_large_content_uses_long_form_length() {
+        // 200 booleans nested in an array forces the array's own content
+        // length past 128 bytes, exercising the long-form length encoding.
+        let items: Vec<IECData> = (0..200).map(|i| IECData::boolean(i % 2 == 0)).collect();
+        let value = IECData::array(items);
+        let mut buf = Vec::new();
+        value.encode_ber(&mut buf);
+        assert_eq!(buf[1] & 0x80, 0x80, "expected long-form length byte");
+        round_trip(value);
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_length() {
+        let truncated = [BER_TAG_BOOLEAN, 0x82, 0x01]; // long form claims 2 length octets, only 1 present
+        assert!(IECData::decode_ber(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_content() {
+        let truncated = [BER_TAG_BOOLEAN, 0x05, 0xFF]; // declares 5 content bytes, only 1 present
+        assert!(IECData::decode_ber(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_constructed_content_not_exactly_consumed() {
+        // One boolean child (3 bytes: tag, len, value) plus a trailing junk
+        // byte that isn't a valid child TLV of its own: the structure's
+        // declared length includes bytes its children can't account for.
+        let mut content = Vec::new();
+        IECData::boolean(true).encode_ber(&mut content);
+        content.push(0x00);
+
+        let mut buf = vec![BER_TAG_STRUCTURE, content.len() as u8];
+        buf.extend_from_slice(&content);
+        assert!(IECData::decode_ber(&buf).is_err());
+    }
+}
+
@@ -0,0 +1,194 @@
+//! Batched GOOSE frame transmission.
+//!
+//! `spawn_pcs_goose_publisher_thread` sends one encoded GOOSE frame per
+//! `send_to` call, so a retransmit burst of N frames (e.g. every PCS's frame
+//! resetting together on new PLC data) costs N send syscalls. On Linux,
+//! `libc::sendmmsg` flushes a whole batch of independent datagrams in a
+//! single syscall; [`publish_frames_vectored`] wraps that, falling back to a
+//! plain loop of individual `send` calls on platforms lacking `sendmmsg` -
+//! the same `cfg(target_os = "linux")` backend split `crate::os::rt_platform`
+//! uses for real-time thread setup. [`VectoredSendBuffers`] is the
+//! zero-allocation-steady-state variant for a long-lived real-time sender
+//! thread, mirroring the caller-owned reusable buffer pattern
+//! `serialize_stpcsimage_into`/`send_stpcsimage_udp_with_buf` already use.
+//!
+//! This operates on a raw socket fd rather than `pnet_datalink`'s
+//! `DataLinkSender` trait object, since that trait has no portable way to
+//! hand back the underlying fd; a caller sending GOOSE frames over a raw
+//! `AF_PACKET` socket it owns directly (instead of through `pnet_datalink`)
+//! can batch its retransmit bursts through this.
+
+use std::io;
+use std::os::unix::io::RawFd;
+
+/// Flush `frames` to `fd` in as few syscalls as possible, allocating fresh
+/// scratch buffers for this one call. Prefer [`VectoredSendBuffers`] on a
+/// hot/real-time path where that per-call allocation matters.
+///
+/// A short result (`Ok(n)` with `n < frames.len()`) means the tail of
+/// `frames` was not sent; the caller should retry those or log a warning,
+/// mirroring `sendmmsg`'s own partial-send convention.
+///
+/// # Errors
+/// Returns `Err` only if not even the first frame could be sent.
+pub fn publish_frames_vectored(fd: RawFd, frames: &[&[u8]]) -> io::Result<usize> {
+    VectoredSendBuffers::new().publish(fd, frames)
+}
+
+/// Reusable `sendmmsg`/fallback scratch buffers for a long-lived sender
+/// thread: the `iovec`/`mmsghdr` arrays `publish` needs are sized once and
+/// grown only if a later batch is larger, so steady-state bursts cost no
+/// heap traffic - the same discipline `plc_retransmit`'s reused `send_buf`
+/// and `serialize_stpcsimage_into`'s caller-owned buffer already follow.
+#[derive(Default)]
+pub struct VectoredSendBuffers {
+    #[cfg(target_os = "linux")]
+    iovecs: Vec<libc::iovec>,
+    #[cfg(target_os = "linux")]
+    msgs: Vec<libc::mmsghdr>,
+}
+
+impl VectoredSendBuffers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flush `frames` to `fd` in as few syscalls as possible, returning the
+    /// number of frames the kernel accepted. See [`publish_frames_vectored`]
+    /// for the partial-send/error convention.
+    pub fn publish(&mut self, fd: RawFd, frames: &[&[u8]]) -> io::Result<usize> {
+        if frames.is_empty() {
+            return Ok(0);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.publish_sendmmsg(fd, frames)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            publish_frames_loop(fd, frames)
+        }
+    }
+
+    /// Linux backend: one `sendmmsg` syscall for the whole batch, retried
+    /// on each `EINTR` since that means the kernel sent nothing, not a
+    /// partial batch.
+    #[cfg(target_os = "linux")]
+    fn publish_sendmmsg(&mut self, fd: RawFd, frames: &[&[u8]]) -> io::Result<usize> {
+        self.iovecs.clear();
+        self.iovecs
+            .extend(frames.iter().map(|frame| libc::iovec {
+                iov_base: frame.as_ptr() as *mut libc::c_void,
+                iov_len: frame.len(),
+            }));
+
+        self.msgs.clear();
+        self.msgs
+            .extend(self.iovecs.iter_mut().map(|iov| libc::mmsghdr {
+                msg_hdr: libc::msghdr {
+                    msg_name: std::ptr::null_mut(),
+                    msg_namelen: 0,
+                    msg_iov: iov as *mut libc::iovec,
+                    msg_iovlen: 1,
+                    msg_control: std::ptr::null_mut(),
+                    msg_controllen: 0,
+                    msg_flags: 0,
+                },
+                msg_len: 0,
+            }));
+
+        loop {
+            // SAFETY: `self.msgs` and the `self.iovecs` it points into outlive
+            // this call, each iovec points at a live `frames[i]` slice
+            // borrowed for the same duration, and `fd` is a socket owned by
+            // the caller.
+            let sent =
+                unsafe { libc::sendmmsg(fd, self.msgs.as_mut_ptr(), self.msgs.len() as u32, 0) };
+            if sent < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            return Ok(sent as usize);
+        }
+    }
+}
+
+/// Fallback backend for platforms without `sendmmsg`: one `send` call per frame.
+#[cfg(not(target_os = "linux"))]
+fn publish_frames_loop(fd: RawFd, frames: &[&[u8]]) -> io::Result<usize> {
+    let mut sent = 0;
+    for frame in frames {
+        // Retry this exact frame on EINTR - unlike advancing to the next
+        // frame, which would silently drop it without the caller ever
+        // seeing an error or a short count that covers it.
+        loop {
+            // SAFETY: `frame` is a live slice for the duration of this call
+            // and `fd` is a socket owned by the caller.
+            let n =
+                unsafe { libc::send(fd, frame.as_ptr() as *const libc::c_void, frame.len(), 0) };
+            if n < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                if sent == 0 {
+                    return Err(err);
+                }
+                return Ok(sent);
+            }
+            break;
+        }
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::io::AsRawFd;
+    use std::os::unix::net::UnixDatagram;
+
+    #[test]
+    fn test_publish_frames_vectored_empty_is_noop() {
+        let (sock, _peer) = UnixDatagram::pair().expect("create socketpair");
+        assert_eq!(publish_frames_vectored(sock.as_raw_fd(), &[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_publish_frames_vectored_sends_every_frame() {
+        let (sock, peer) = UnixDatagram::pair().expect("create socketpair");
+
+        let frames: Vec<&[u8]> = vec![b"frame-one", b"frame-two", b"frame-three"];
+        let sent = publish_frames_vectored(sock.as_raw_fd(), &frames).expect("send should succeed");
+        assert_eq!(sent, frames.len());
+
+        let mut buf = [0u8; 64];
+        for expected in &frames {
+            let n = peer.recv(&mut buf).expect("recv should succeed");
+            assert_eq!(&buf[..n], *expected);
+        }
+    }
+
+    #[test]
+    fn test_vectored_send_buffers_reused_across_calls() {
+        let (sock, peer) = UnixDatagram::pair().expect("create socketpair");
+        let mut bufs = VectoredSendBuffers::new();
+
+        let first: Vec<&[u8]> = vec![b"one", b"two"];
+        assert_eq!(bufs.publish(sock.as_raw_fd(), &first).unwrap(), 2);
+
+        let second: Vec<&[u8]> = vec![b"three"];
+        assert_eq!(bufs.publish(sock.as_raw_fd(), &second).unwrap(), 1);
+
+        let mut buf = [0u8; 64];
+        for expected in [b"one".as_slice(), b"two".as_slice(), b"three".as_slice()] {
+            let n = peer.recv(&mut buf).expect("recv should succeed");
+            assert_eq!(&buf[..n], expected);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Bounded, metered ingress queue for captured packets, replacing an
+//! unbounded `crossbeam_channel` between capture thread(s) and worker
+//! threads. Capacity is fixed at construction; when full, the configured
+//! [`OverflowPolicy`] decides whether to discard the oldest queued packet
+//! (making room for the new one) or the incoming packet, either way
+//! incrementing a drop counter instead of blocking the pusher - the capture
+//! thread feeding this queue must never stall waiting for a worker to drain
+//! it.
+
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// What to do when the ingress queue is full and a new item arrives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued item to make room for the new one.
+    DropOldest,
+    /// Discard the incoming item, leaving the queue unchanged.
+    DropNewest,
+}
+
+#[derive(Debug, Default)]
+struct IngressMetrics {
+    high_water_mark: AtomicUsize,
+    dropped_count: AtomicU64,
+}
+
+/// A point-in-time snapshot of one [`IngressQueue`]'s depth, high-water
+/// mark, and drop count, for operators to poll (e.g. to size the buffer or
+/// detect when workers are falling behind real time).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IngressQueueMetrics {
+    pub depth: usize,
+    pub high_water_mark: usize,
+    pub dropped_count: u64,
+}
+
+/// A bounded packet queue with a configurable overflow policy. Cloning
+/// shares the same underlying channel and counters (cheap handle, like
+/// `crossbeam_channel::Sender`/`Receiver` themselves).
+pub struct IngressQueue<T> {
+    tx: Sender<T>,
+    rx: Receiver<T>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    metrics: Arc<IngressMetrics>,
+}
+
+impl<T> Clone for IngressQueue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            rx: self.rx.clone(),
+            capacity: self.capacity,
+            policy: self.policy,
+            metrics: Arc::clone(&self.metrics),
+        }
+    }
+}
+
+impl<T> IngressQueue<T> {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        let (tx, rx) = bounded(capacity);
+        Self {
+            tx,
+            rx,
+            capacity,
+            policy,
+            metrics: Arc::new(IngressMetrics::default()),
+        }
+    }
+
+    /// The receiving half, for a worker thread to `recv()`/`recv_timeout()`
+    /// from directly, same as it would an unbounded channel's `Receiver`.
+    pub fn receiver(&self) -> &Receiver<T> {
+        &self.rx
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Enqueue `item`, applying the configured overflow policy if the queue
+    /// is already at capacity. Never blocks.
+    pub fn push(&self, item: T) {
+        match self.tx.try_send(item) {
+            Ok(()) => self.record_high_water_mark(),
+            Err(TrySendError::Full(item)) => self.handle_full(item),
+            Err(TrySendError::Disconnected(_)) => {
+                // No receivers left; nothing more this queue can do.
+            }
+        }
+    }
+
+    fn handle_full(&self, item: T) {
+        match self.policy {
+            OverflowPolicy::DropNewest => {
+                self.metrics.dropped_count.fetch_add(1, Ordering::Relaxed);
+            }
+            OverflowPolicy::DropOldest => {
+                // Make room by discarding the oldest queued item, then retry
+                // once. If it's full again (a concurrent producer raced in
+                // ahead of us), fall back to dropping the incoming item
+                // rather than looping.
+                if self.rx.try_recv().is_ok() {
+                    self.metrics.dropped_count.fetch_add(1, Ordering::Relaxed);
+                    match self.tx.try_send(item) {
+                        Ok(()) => self.record_high_water_mark(),
+                        Err(_) => {
+                            self.metrics.dropped_count.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                } else {
+                    self.metrics.dropped_count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    fn record_high_water_mark(&self) {
+        self.metrics.high_water_mark.fetch_max(self.tx.len(), Ordering::Relaxed);
+    }
+
+    /// A point-in-time snapshot of this queue's current depth, high-water
+    /// mark, and cumulative drop count.
+    pub fn metrics(&self) -> IngressQueueMetrics {
+        IngressQueueMetrics {
+            depth: self.tx.len(),
+            high_water_mark: self.metrics.high_water_mark.load(Ordering::Relaxed),
+            dropped_count: self.metrics.dropped_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_under_capacity_does_not_drop() {
+        let queue = IngressQueue::new(4, OverflowPolicy::DropNewest);
+        for i in 0..4 {
+            queue.push(i);
+        }
+        assert_eq!(queue.metrics().dropped_count, 0);
+        assert_eq!(queue.metrics().depth, 4);
+    }
+
+    #[test]
+    fn test_drop_newest_keeps_oldest_items_and_counts_drop() {
+        let queue = IngressQueue::new(2, OverflowPolicy::DropNewest);
+        queue.push(1);
+        queue.push(2);
+        queue.push(3); // dropped: queue stays [1, 2]
+
+        assert_eq!(queue.metrics().dropped_count, 1);
+        assert_eq!(queue.receiver().recv().unwrap(), 1);
+        assert_eq!(queue.receiver().recv().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_newest_items_and_counts_drop() {
+        let queue = IngressQueue::new(2, OverflowPolicy::DropOldest);
+        queue.push(1);
+        queue.push(2);
+        queue.push(3); // 1 is discarded to make room: queue becomes [2, 3]
+
+        assert_eq!(queue.metrics().dropped_count, 1);
+        assert_eq!(queue.receiver().recv().unwrap(), 2);
+        assert_eq!(queue.receiver().recv().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_high_water_mark_tracks_peak_depth_not_current_depth() {
+        let queue = IngressQueue::new(4, OverflowPolicy::DropOldest);
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
+        assert_eq!(queue.metrics().high_water_mark, 3);
+
+        let _ = queue.receiver().recv().unwrap();
+        let _ = queue.receiver().recv().unwrap();
+        assert_eq!(queue.metrics().depth, 1);
+        assert_eq!(queue.metrics().high_water_mark, 3);
+    }
+
+    #[test]
+    fn test_clone_shares_the_same_queue_and_metrics() {
+        let queue = IngressQueue::new(1, OverflowPolicy::DropNewest);
+        let handle = queue.clone();
+        queue.push(1);
+        assert_eq!(handle.receiver().recv().unwrap(), 1);
+
+        handle.push(2);
+        handle.push(3); // dropped, counted on the shared metrics
+        assert_eq!(queue.metrics().dropped_count, 1);
+    }
+
+    #[test]
+    fn test_capacity_reports_constructed_value() {
+        let queue: IngressQueue<u8> = IngressQueue::new(16, OverflowPolicy::DropOldest);
+        assert_eq!(queue.capacity(), 16);
+    }
+}
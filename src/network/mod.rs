@@ -1,6 +1,15 @@
+pub mod ingress_queue;
+pub mod ptp_clock;
+
+pub use ingress_queue::{IngressQueue, IngressQueueMetrics, OverflowPolicy};
+pub use ptp_clock::{spawn_ptp_listener, PtpClock, MAX_GOOSE_TIMESTAMP_SKEW};
+
 use anyhow::Result;
+use crossbeam_channel::{unbounded, Receiver};
 use log::{error, info, warn};
-use pnet_datalink::{self, Channel, DataLinkReceiver, DataLinkSender};
+use pnet_datalink::{self, Channel, DataLinkReceiver, DataLinkSender, NetworkInterface};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 /// Network channel setup result containing transmitters and receivers for both LANs
 pub struct NetworkChannels {
@@ -89,3 +98,129 @@ pub fn setup_network_channels(
         rx_lan2: rx_lan2_opt,
     })
 }
+
+/// A freshly (re)created sender/receiver pair for one LAN interface, handed
+/// from [`spawn_interface_supervisor`] to the worker/sender threads over its
+/// returned swap channel.
+pub struct ChannelUpdate {
+    pub tx: Box<dyn DataLinkSender>,
+    pub rx: Box<dyn DataLinkReceiver>,
+}
+
+/// Exponential backoff schedule for interface reconnection attempts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffSchedule {
+    pub initial_ms: u64,
+    pub max_ms: u64,
+}
+
+impl Default for BackoffSchedule {
+    /// 1s initial wait, doubling up to a 300s (5 minute) cap.
+    fn default() -> Self {
+        Self {
+            initial_ms: 1_000,
+            max_ms: 300_000,
+        }
+    }
+}
+
+impl BackoffSchedule {
+    /// The wait after the next failed attempt, given the wait just used.
+    fn next(&self, current_ms: u64) -> u64 {
+        current_ms.saturating_mul(2).min(self.max_ms)
+    }
+}
+
+/// Owns (re)creation of one LAN's `pnet_datalink` channel so a down or
+/// flapping NIC doesn't permanently degrade that LAN. Creates the channel
+/// immediately; once established, it blocks on `failure_rx` (signaled by the
+/// worker/sender threads when a send/receive error indicates the link is
+/// gone) before attempting to rebuild it. A rebuild that fails retries on
+/// `backoff`, doubling the wait on each failed attempt up to `backoff.max_ms`
+/// and resetting to `backoff.initial_ms` as soon as a channel succeeds.
+/// Every freshly created sender/receiver pair is sent on the returned
+/// channel for the worker/sender threads to swap in.
+pub fn spawn_interface_supervisor(
+    interface: NetworkInterface,
+    lan_id: u16,
+    backoff: BackoffSchedule,
+    failure_rx: Receiver<()>,
+) -> (Receiver<ChannelUpdate>, JoinHandle<()>) {
+    let (update_tx, update_rx) = unbounded();
+
+    let handle = thread::spawn(move || loop {
+        let mut wait_ms = backoff.initial_ms;
+        loop {
+            match pnet_datalink::channel(&interface, Default::default()) {
+                Ok(Channel::Ethernet(tx, rx)) => {
+                    info!(
+                        "LAN{} interface {}: channel (re)established",
+                        lan_id, interface.name
+                    );
+                    if update_tx.send(ChannelUpdate { tx, rx }).is_err() {
+                        info!("LAN{} supervisor: no receiver left, shutting down", lan_id);
+                        return;
+                    }
+                    break;
+                }
+                Ok(_) => {
+                    warn!(
+                        "LAN{} interface {}: unhandled channel type, retrying in {}ms",
+                        lan_id, interface.name, wait_ms
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "LAN{} interface {}: channel creation failed: {}, retrying in {}ms",
+                        lan_id, interface.name, e, wait_ms
+                    );
+                }
+            }
+            thread::sleep(Duration::from_millis(wait_ms));
+            wait_ms = backoff.next(wait_ms);
+        }
+
+        // Channel is up; wait for a reported failure before rebuilding it.
+        if failure_rx.recv().is_err() {
+            info!("LAN{} supervisor: no failure reporters left, shutting down", lan_id);
+            return;
+        }
+        warn!("LAN{} interface {}: link failure reported, reconnecting", lan_id, interface.name);
+    });
+
+    (update_rx, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_schedule_doubles_up_to_cap() {
+        let backoff = BackoffSchedule { initial_ms: 1_000, max_ms: 300_000 };
+        let mut wait_ms = backoff.initial_ms;
+        for _ in 0..20 {
+            wait_ms = backoff.next(wait_ms);
+            assert!(wait_ms <= backoff.max_ms);
+        }
+        assert_eq!(wait_ms, backoff.max_ms);
+    }
+
+    #[test]
+    fn test_backoff_schedule_default_matches_documented_values() {
+        let backoff = BackoffSchedule::default();
+        assert_eq!(backoff.initial_ms, 1_000);
+        assert_eq!(backoff.max_ms, 300_000);
+    }
+
+    #[test]
+    fn test_backoff_schedule_next_doubles_then_caps() {
+        let backoff = BackoffSchedule { initial_ms: 50, max_ms: 200 };
+        assert_eq!(backoff.next(50), 100);
+        assert_eq!(backoff.next(100), 200);
+        assert_eq!(backoff.next(200), 200); // capped
+                                             // A fresh outer loop iteration restarts from initial_ms, not from the
+                                             // capped value - exercised in spawn_interface_supervisor itself.
+        assert_eq!(backoff.initial_ms, 50);
+    }
+}
@@ -0,0 +1,327 @@
+//! A minimal IEEE 1588 (PTP) client providing a network-disciplined time
+//! source for GOOSE timestamps, in place of judging freshness purely on
+//! stNum/sqNum against an undisciplined `SystemTime`.
+//!
+//! Scope: this listens for `Sync`/`Follow_Up` multicast traffic and tracks
+//! the resulting grandmaster offset. It deliberately does **not** implement
+//! the Best Master Clock Algorithm (no `Announce` handling - the grandmaster
+//! is assumed pre-selected, e.g. the substation's sole PTP master) or path
+//! delay measurement (no `Delay_Req`/`Delay_Resp` exchange). Offsets are
+//! therefore accurate to within one-way network latency rather than true
+//! round-trip-corrected PTP accuracy, which on a substation LAN is
+//! sub-millisecond - well inside the [`crate::goose::pdu::Iec61850Time::ACCURACY_UNSPECIFIED`]
+//! this simulator already reports. A full BMCA/path-delay client would need
+//! an external PTP stack; this is the "dependency-light" alternative.
+//!
+//! [`PtpClock`] is a cheap, cloneable handle (like `crossbeam_channel`'s
+//! `Sender`/`Receiver`): clone it into each thread that needs to read or
+//! discipline the clock.
+
+use crate::goose::pdu::Iec61850Time;
+use log::{info, warn};
+use std::net::{Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// PTP event message port (`Sync`, `Delay_Req`) per IEEE 1588-2008 Annex D.
+const PTP_EVENT_PORT: u16 = 319;
+/// PTP general message port (`Follow_Up`, `Delay_Resp`, `Announce`).
+const PTP_GENERAL_PORT: u16 = 320;
+/// The standard PTP primary multicast group.
+const PTP_MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 1, 129);
+
+const PTP_MSG_SYNC: u8 = 0x0;
+const PTP_MSG_FOLLOW_UP: u8 = 0x8;
+/// Length of the PTPv2 common header preceding any message-specific fields.
+const PTP_HEADER_LEN: usize = 34;
+/// `flagField` bit indicating a two-step clock (the precise origin timestamp
+/// arrives in a separate `Follow_Up`, not in the `Sync` message itself).
+const PTP_FLAG_TWO_STEP: u16 = 0x0002;
+
+/// How long a previously accepted offset is trusted before [`PtpClock::now`]
+/// falls back to undisciplined `SystemTime`. A grandmaster typically sends
+/// `Sync` every 1-2s; this tolerates a handful of missed intervals before
+/// declaring the clock stale.
+const SYNC_STALENESS: Duration = Duration::from_secs(10);
+
+/// How far a GOOSE PDU's `t` field may diverge from PTP time before
+/// [`PtpClock::validate_goose_timestamp`] flags it as implausible.
+pub const MAX_GOOSE_TIMESTAMP_SKEW: Duration = Duration::from_secs(5);
+
+/// A PTP-disciplined clock handle. Tracks the offset from the last accepted
+/// grandmaster timestamp and exposes [`now`](Self::now) with a validity flag,
+/// falling back to undisciplined `SystemTime` when nothing has been accepted
+/// within [`SYNC_STALENESS`].
+#[derive(Clone)]
+pub struct PtpClock {
+    /// Grandmaster time minus local time, in whole nanoseconds, as of the
+    /// last accepted `Sync`/`Follow_Up`.
+    offset_nanos: Arc<AtomicI64>,
+    synchronized: Arc<AtomicBool>,
+    last_sync_epoch_secs: Arc<AtomicI64>,
+}
+
+impl Default for PtpClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PtpClock {
+    pub fn new() -> Self {
+        Self {
+            offset_nanos: Arc::new(AtomicI64::new(0)),
+            synchronized: Arc::new(AtomicBool::new(false)),
+            last_sync_epoch_secs: Arc::new(AtomicI64::new(0)),
+        }
+    }
+
+    fn record_offset(&self, offset_nanos: i64) {
+        self.offset_nanos.store(offset_nanos, Ordering::Relaxed);
+        self.synchronized.store(true, Ordering::Relaxed);
+        let now_secs = epoch_secs(SystemTime::now());
+        self.last_sync_epoch_secs.store(now_secs, Ordering::Relaxed);
+    }
+
+    fn is_synchronized(&self) -> bool {
+        if !self.synchronized.load(Ordering::Relaxed) {
+            return false;
+        }
+        let last = self.last_sync_epoch_secs.load(Ordering::Relaxed);
+        let now = epoch_secs(SystemTime::now());
+        now.saturating_sub(last) <= SYNC_STALENESS.as_secs() as i64
+    }
+
+    /// Current time and whether it's presently PTP-disciplined (a recent
+    /// `Sync`/`Follow_Up` was accepted within [`SYNC_STALENESS`]). Falls back
+    /// to undisciplined `SystemTime::now()` - with `false` - once stale.
+    pub fn now(&self) -> (SystemTime, bool) {
+        let local_now = SystemTime::now();
+        if !self.is_synchronized() {
+            return (local_now, false);
+        }
+        let offset = self.offset_nanos.load(Ordering::Relaxed);
+        (apply_offset(local_now, offset), true)
+    }
+
+    /// This clock's current time as an [`Iec61850Time`], with
+    /// `clock_not_synchronized` set whenever [`now`](Self::now) is falling
+    /// back to undisciplined `SystemTime`.
+    pub fn iec61850_time(&self) -> Iec61850Time {
+        let (t, synced) = self.now();
+        let since_epoch = t.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Iec61850Time::new(
+            since_epoch.as_secs() as u32,
+            since_epoch.subsec_nanos(),
+            true,
+            false,
+            !synced,
+            Iec61850Time::ACCURACY_UNSPECIFIED,
+        )
+    }
+
+    /// Compare a received GOOSE PDU's `t` field against this clock's current
+    /// time, rejecting (`false`) one whose skew exceeds `max_skew` - a clock
+    /// well outside sane bounds, e.g. a misconfigured publisher or a replayed/
+    /// spoofed frame. Always accepts while this clock isn't itself
+    /// PTP-synchronized, since an undisciplined local clock has no sound
+    /// basis to reject wall-clock time it hasn't reliably established itself.
+    pub fn validate_goose_timestamp(&self, t: Iec61850Time, max_skew: Duration) -> bool {
+        let (now, synced) = self.now();
+        if !synced {
+            return true;
+        }
+        let fraction_nanos = ((t.fraction() as u64) * 1_000_000_000 / (1 << 24)) as u32;
+        let t_system = UNIX_EPOCH + Duration::new(t.seconds() as u64, fraction_nanos);
+        match now.duration_since(t_system) {
+            Ok(skew) => skew <= max_skew,
+            Err(e) => e.duration() <= max_skew,
+        }
+    }
+}
+
+fn epoch_secs(t: SystemTime) -> i64 {
+    t.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+}
+
+fn apply_offset(local_now: SystemTime, offset_nanos: i64) -> SystemTime {
+    if offset_nanos >= 0 {
+        local_now + Duration::from_nanos(offset_nanos as u64)
+    } else {
+        local_now - Duration::from_nanos((-offset_nanos) as u64)
+    }
+}
+
+/// Spawn a thread listening for PTP `Sync`/`Follow_Up` multicast traffic on
+/// `bind_addr` (the LAN interface address to join the PTP multicast group
+/// from) and disciplining `clock` whenever a precise origin timestamp is
+/// accepted. Two-step `Sync` messages are ignored in favor of their matching
+/// `Follow_Up`; one-step `Sync` messages carry a precise origin timestamp
+/// themselves and are applied directly. `Delay_Req`/`Delay_Resp` and
+/// `Announce` are not handled - see the module doc comment.
+pub fn spawn_ptp_listener(bind_addr: Ipv4Addr, clock: PtpClock) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let general_socket = match UdpSocket::bind((bind_addr, PTP_GENERAL_PORT)) {
+            Ok(socket) => socket,
+            Err(e) => {
+                warn!("PTP listener: failed to bind port {}: {}", PTP_GENERAL_PORT, e);
+                return;
+            }
+        };
+        if let Err(e) = general_socket.join_multicast_v4(&PTP_MULTICAST_GROUP, &bind_addr) {
+            warn!("PTP listener: failed to join multicast group on {}: {}", bind_addr, e);
+        }
+        let _ = general_socket.set_read_timeout(Some(Duration::from_millis(200)));
+
+        let event_socket = match UdpSocket::bind((bind_addr, PTP_EVENT_PORT)) {
+            Ok(socket) => {
+                if let Err(e) = socket.join_multicast_v4(&PTP_MULTICAST_GROUP, &bind_addr) {
+                    warn!("PTP listener: failed to join multicast group on {}: {}", bind_addr, e);
+                }
+                let _ = socket.set_read_timeout(Some(Duration::from_millis(200)));
+                Some(socket)
+            }
+            Err(e) => {
+                warn!("PTP listener: failed to bind port {}: {}", PTP_EVENT_PORT, e);
+                None
+            }
+        };
+
+        info!("PTP listener started on {}", bind_addr);
+        let mut buf = [0u8; 256];
+        loop {
+            if let Ok((len, _src)) = general_socket.recv_from(&mut buf) {
+                handle_ptp_message(&buf[..len], &clock);
+            }
+            if let Some(ref socket) = event_socket {
+                if let Ok((len, _src)) = socket.recv_from(&mut buf) {
+                    handle_ptp_message(&buf[..len], &clock);
+                }
+            }
+        }
+    })
+}
+
+fn handle_ptp_message(frame: &[u8], clock: &PtpClock) {
+    if frame.len() < PTP_HEADER_LEN + 10 {
+        return;
+    }
+    let message_type = frame[0] & 0x0F;
+    let two_step = u16::from_be_bytes([frame[6], frame[7]]) & PTP_FLAG_TWO_STEP != 0;
+    match message_type {
+        PTP_MSG_SYNC if !two_step => apply_origin_timestamp(frame, clock),
+        PTP_MSG_FOLLOW_UP => apply_origin_timestamp(frame, clock),
+        _ => {}
+    }
+}
+
+/// Decode the 10-byte `originTimestamp`/`preciseOriginTimestamp` (6-byte
+/// seconds + 4-byte nanoseconds) immediately following the common header and
+/// record the offset from this receiver's local clock.
+fn apply_origin_timestamp(frame: &[u8], clock: &PtpClock) {
+    let ts = &frame[PTP_HEADER_LEN..PTP_HEADER_LEN + 10];
+    let mut seconds_bytes = [0u8; 8];
+    seconds_bytes[2..8].copy_from_slice(&ts[0..6]);
+    let seconds = u64::from_be_bytes(seconds_bytes);
+    let nanos = u32::from_be_bytes(ts[6..10].try_into().unwrap());
+    let origin = UNIX_EPOCH + Duration::new(seconds, nanos);
+    let local_now = SystemTime::now();
+
+    let offset_nanos: i64 = match origin.duration_since(local_now) {
+        Ok(d) => d.as_nanos() as i64,
+        Err(e) => -(e.duration().as_nanos() as i64),
+    };
+    clock.record_offset(offset_nanos);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sync_frame(message_type: u8, two_step: bool, origin: SystemTime) -> [u8; PTP_HEADER_LEN + 10] {
+        let mut frame = [0u8; PTP_HEADER_LEN + 10];
+        frame[0] = message_type & 0x0F;
+        if two_step {
+            frame[6..8].copy_from_slice(&PTP_FLAG_TWO_STEP.to_be_bytes());
+        }
+        let since_epoch = origin.duration_since(UNIX_EPOCH).unwrap();
+        let seconds = since_epoch.as_secs().to_be_bytes();
+        frame[PTP_HEADER_LEN..PTP_HEADER_LEN + 6].copy_from_slice(&seconds[2..8]);
+        frame[PTP_HEADER_LEN + 6..PTP_HEADER_LEN + 10]
+            .copy_from_slice(&since_epoch.subsec_nanos().to_be_bytes());
+        frame
+    }
+
+    #[test]
+    fn test_new_clock_is_unsynchronized_and_falls_back_to_system_time() {
+        let clock = PtpClock::new();
+        let (_, synced) = clock.now();
+        assert!(!synced);
+    }
+
+    #[test]
+    fn test_one_step_sync_disciplines_the_clock() {
+        let clock = PtpClock::new();
+        let origin = SystemTime::now() + Duration::from_millis(100);
+        let frame = sync_frame(PTP_MSG_SYNC, false, origin);
+        handle_ptp_message(&frame, &clock);
+
+        let (_, synced) = clock.now();
+        assert!(synced);
+        let offset = clock.offset_nanos.load(Ordering::Relaxed);
+        assert!((50_000_000..150_000_000).contains(&offset), "offset was {offset}");
+    }
+
+    #[test]
+    fn test_two_step_sync_is_ignored_until_follow_up() {
+        let clock = PtpClock::new();
+        let sync = sync_frame(PTP_MSG_SYNC, true, SystemTime::now());
+        handle_ptp_message(&sync, &clock);
+        assert!(!clock.now().1, "two-step Sync alone must not discipline the clock");
+
+        let follow_up = sync_frame(PTP_MSG_FOLLOW_UP, true, SystemTime::now());
+        handle_ptp_message(&follow_up, &clock);
+        assert!(clock.now().1, "Follow_Up should discipline the clock");
+    }
+
+    #[test]
+    fn test_synchronization_goes_stale_after_staleness_window() {
+        let clock = PtpClock::new();
+        clock.record_offset(0);
+        assert!(clock.is_synchronized());
+
+        let stale_secs = epoch_secs(SystemTime::now()) - SYNC_STALENESS.as_secs() as i64 - 1;
+        clock.last_sync_epoch_secs.store(stale_secs, Ordering::Relaxed);
+        assert!(!clock.is_synchronized());
+        assert!(!clock.now().1);
+    }
+
+    #[test]
+    fn test_validate_goose_timestamp_unsynchronized_always_accepts() {
+        let clock = PtpClock::new();
+        let ancient = Iec61850Time::new(0, 0, true, false, false, Iec61850Time::ACCURACY_UNSPECIFIED);
+        assert!(clock.validate_goose_timestamp(ancient, MAX_GOOSE_TIMESTAMP_SKEW));
+    }
+
+    #[test]
+    fn test_validate_goose_timestamp_rejects_implausible_skew_once_synchronized() {
+        let clock = PtpClock::new();
+        clock.record_offset(0);
+
+        let current = clock.iec61850_time();
+        assert!(clock.validate_goose_timestamp(current, MAX_GOOSE_TIMESTAMP_SKEW));
+
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let stale = Iec61850Time::new(
+            (since_epoch.as_secs() - 1000) as u32,
+            0,
+            true,
+            false,
+            false,
+            Iec61850Time::ACCURACY_UNSPECIFIED,
+        );
+        assert!(!clock.validate_goose_timestamp(stale, MAX_GOOSE_TIMESTAMP_SKEW));
+    }
+}
@@ -0,0 +1,230 @@
+// Host health & throughput monitoring subsystem.
+//
+// Samples host metrics (CPU load, memory usage, per-interface network byte/packet
+// rates, uptime) from /proc the way systemstat does on Linux, and fuses them with
+// simulator-internal counters so a stalled RX path is detectable downstream.
+
+use log::{error, warn};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Per-interface byte/packet counters read from `/proc/net/dev`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InterfaceCounters {
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+}
+
+/// Per-interface rates derived from two consecutive `InterfaceCounters` samples.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InterfaceRates {
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub rx_packets_per_sec: f64,
+    pub tx_packets_per_sec: f64,
+}
+
+/// Simulator-internal counters that are fused with host metrics.
+#[derive(Debug, Default)]
+pub struct GooseCounters {
+    pub decoded: AtomicU64,
+    pub decode_failures: AtomicU64,
+    pub appid_matched: AtomicU64,
+    pub appid_unmatched: AtomicU64,
+}
+
+impl GooseCounters {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn record_decoded(&self, matched: bool) {
+        self.decoded.fetch_add(1, Ordering::Relaxed);
+        if matched {
+            self.appid_matched.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.appid_unmatched.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn record_decode_failure(&self) {
+        self.decode_failures.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A point-in-time view of host health plus simulator-internal counters.
+///
+/// Serializable so it can be embedded next to `StPCSImage` or published on its own.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HealthSnapshot {
+    pub uptime_secs: u64,
+    pub cpu_load_percent: f32,
+    pub mem_used_bytes: u64,
+    pub mem_total_bytes: u64,
+    /// Per-interface (lan_id label) throughput, e.g. "lan1" -> rates.
+    pub interface_rates: HashMap<String, InterfaceRates>,
+    pub goose_frames_decoded_per_sec: HashMap<u16, f64>,
+    pub decode_failure_rate: f64,
+    pub buffer_pool_occupancy: usize,
+    pub appid_matched: u64,
+    pub appid_unmatched: u64,
+}
+
+/// Samples host metrics on a cadence and fuses them with simulator counters.
+///
+/// Drives `lifecounter`-style liveness by tracking wall-clock deltas between
+/// samples: a caller that never sees `HealthMonitor::sample()` advance the
+/// frame counters can treat the RX path as stalled.
+pub struct HealthMonitor {
+    start: Instant,
+    goose_counters: HashMap<u16, Arc<GooseCounters>>,
+    last_proc_samples: HashMap<String, InterfaceCounters>,
+    last_sample_at: Instant,
+}
+
+impl HealthMonitor {
+    pub fn new(goose_counters: HashMap<u16, Arc<GooseCounters>>) -> Self {
+        Self {
+            start: Instant::now(),
+            goose_counters,
+            last_proc_samples: HashMap::new(),
+            last_sample_at: Instant::now(),
+        }
+    }
+
+    /// Read `/proc/loadavg`, `/proc/meminfo`, `/proc/net/dev`, fuse with the simulator
+    /// counters registered per `lan_id`, and return a fresh `HealthSnapshot`.
+    pub fn sample(&mut self, interfaces: &[&str], buffer_pool_occupancy: usize) -> HealthSnapshot {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_sample_at).as_secs_f64().max(0.001);
+
+        let cpu_load_percent = read_loadavg_percent().unwrap_or(0.0);
+        let (mem_used_bytes, mem_total_bytes) = read_meminfo().unwrap_or((0, 0));
+
+        let mut interface_rates = HashMap::new();
+        for name in interfaces {
+            if let Some(counters) = read_interface_counters(name) {
+                let prev = self.last_proc_samples.get(*name).cloned().unwrap_or_default();
+                interface_rates.insert(
+                    (*name).to_string(),
+                    InterfaceRates {
+                        rx_bytes_per_sec: delta_rate(prev.rx_bytes, counters.rx_bytes, elapsed),
+                        tx_bytes_per_sec: delta_rate(prev.tx_bytes, counters.tx_bytes, elapsed),
+                        rx_packets_per_sec: delta_rate(prev.rx_packets, counters.rx_packets, elapsed),
+                        tx_packets_per_sec: delta_rate(prev.tx_packets, counters.tx_packets, elapsed),
+                    },
+                );
+                self.last_proc_samples.insert((*name).to_string(), counters);
+            } else {
+                warn!("HealthMonitor: could not read counters for interface '{}'", name);
+            }
+        }
+
+        let mut goose_frames_decoded_per_sec = HashMap::new();
+        let mut total_decoded = 0u64;
+        let mut total_failures = 0u64;
+        let mut total_matched = 0u64;
+        let mut total_unmatched = 0u64;
+        for (lan_id, counters) in &self.goose_counters {
+            let decoded = counters.decoded.swap(0, Ordering::Relaxed);
+            total_failures += counters.decode_failures.swap(0, Ordering::Relaxed);
+            total_matched += counters.appid_matched.swap(0, Ordering::Relaxed);
+            total_unmatched += counters.appid_unmatched.swap(0, Ordering::Relaxed);
+            total_decoded += decoded;
+            goose_frames_decoded_per_sec.insert(*lan_id, decoded as f64 / elapsed);
+        }
+
+        let decode_failure_rate = if total_decoded + total_failures > 0 {
+            total_failures as f64 / (total_decoded + total_failures) as f64
+        } else {
+            0.0
+        };
+
+        self.last_sample_at = now;
+
+        HealthSnapshot {
+            uptime_secs: self.start.elapsed().as_secs(),
+            cpu_load_percent,
+            mem_used_bytes,
+            mem_total_bytes,
+            interface_rates,
+            goose_frames_decoded_per_sec,
+            decode_failure_rate,
+            buffer_pool_occupancy,
+            appid_matched: total_matched,
+            appid_unmatched: total_unmatched,
+        }
+    }
+}
+
+fn delta_rate(prev: u64, now: u64, elapsed_secs: f64) -> f64 {
+    now.saturating_sub(prev) as f64 / elapsed_secs
+}
+
+/// Read the 1-minute load average from `/proc/loadavg` as an approximate "percent busy"
+/// figure (load / num_cpus * 100), clamped to the sensible range.
+fn read_loadavg_percent() -> Option<f32> {
+    let content = fs::read_to_string("/proc/loadavg").ok()?;
+    let load1: f32 = content.split_whitespace().next()?.parse().ok()?;
+    let cpus = num_cpus::get().max(1) as f32;
+    Some((load1 / cpus * 100.0).clamp(0.0, 100.0))
+}
+
+/// Read used/total memory in bytes from `/proc/meminfo`.
+fn read_meminfo() -> Option<(u64, u64)> {
+    let content = fs::read_to_string("/proc/meminfo").ok()?;
+    let mut total_kb = None;
+    let mut available_kb = None;
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("MemTotal:") {
+            total_kb = parse_meminfo_kb(rest);
+        } else if let Some(rest) = line.strip_prefix("MemAvailable:") {
+            available_kb = parse_meminfo_kb(rest);
+        }
+    }
+    let total_kb = total_kb?;
+    let available_kb = available_kb.unwrap_or(0);
+    let used_kb = total_kb.saturating_sub(available_kb);
+    Some((used_kb * 1024, total_kb * 1024))
+}
+
+fn parse_meminfo_kb(rest: &str) -> Option<u64> {
+    rest.trim().split_whitespace().next()?.parse().ok()
+}
+
+/// Read cumulative RX/TX byte and packet counters for `interface` from `/proc/net/dev`.
+fn read_interface_counters(interface: &str) -> Option<InterfaceCounters> {
+    let content = fs::read_to_string("/proc/net/dev").ok()?;
+    for line in content.lines().skip(2) {
+        let mut parts = line.splitn(2, ':');
+        let name = parts.next()?.trim();
+        if name != interface {
+            continue;
+        }
+        let fields: Vec<&str> = parts.next()?.split_whitespace().collect();
+        if fields.len() < 16 {
+            error!("/proc/net/dev line for '{}' has unexpected field count", interface);
+            return None;
+        }
+        return Some(InterfaceCounters {
+            rx_bytes: fields[0].parse().ok()?,
+            rx_packets: fields[1].parse().ok()?,
+            tx_bytes: fields[8].parse().ok()?,
+            tx_packets: fields[9].parse().ok()?,
+        });
+    }
+    None
+}
+
+/// Uptime of the host, from `/proc/uptime`, independent of `HealthMonitor::start`.
+pub fn host_uptime() -> Option<Duration> {
+    let content = fs::read_to_string("/proc/uptime").ok()?;
+    let secs: f64 = content.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_secs_f64(secs))
+}
@@ -1,11 +1,14 @@
+use crate::os::rt_platform::RtPlatform;
 use anyhow::Result;
 use libc::{
     clock_gettime, clock_nanosleep, cpu_set_t, mlockall, pthread_self,
     pthread_setaffinity_np, sched_param, sched_setscheduler, timespec,
-    CLOCK_MONOTONIC, CPU_SET, CPU_ZERO, MCL_CURRENT, MCL_FUTURE, SCHED_FIFO, TIMER_ABSTIME,
+    CLOCK_MONOTONIC, CLOCK_REALTIME, CPU_SET, CPU_ZERO, MCL_CURRENT, MCL_FUTURE, SCHED_FIFO,
+    TIMER_ABSTIME,
 };
-use log::{error, info, warn};
+use log::info;
 use std::io;
+use std::time::Duration;
 
 /// Pin the current thread to a specific CPU core
 pub fn pin_thread_to_core(core_id: usize) -> Result<()> {
@@ -95,6 +98,88 @@ pub fn get_monotonic_time() -> Result<timespec> {
     }
 }
 
+/// Get current wall-clock time (affected by NTP/manual adjustments, unlike
+/// `get_monotonic_time`). This is the clock IEC 61850 UtcTime values are
+/// synthesized from; scheduling code must keep using `get_monotonic_time`.
+pub fn get_realtime_clock() -> Result<timespec> {
+    unsafe {
+        let mut ts: timespec = std::mem::zeroed();
+        let res = clock_gettime(CLOCK_REALTIME, &mut ts);
+        if res != 0 {
+            anyhow::bail!(
+                "Failed to get realtime clock: {}",
+                io::Error::last_os_error()
+            );
+        }
+        Ok(ts)
+    }
+}
+
+/// TimeQuality bit layout for octet 7 of an IEC 61850 UtcTime (IEC 61850-7-2 §6.1.2.9).
+const LEAP_SECONDS_KNOWN: u8 = 0b1000_0000;
+const CLOCK_FAILURE: u8 = 0b0100_0000;
+const CLOCK_NOT_SYNCHRONIZED: u8 = 0b0010_0000;
+const TIME_ACCURACY_MASK: u8 = 0b0001_1111;
+
+/// Encode the current wall-clock time as an 8-octet IEC 61850 UtcTime, for
+/// `IECGoosePdu.t` / `IECData::utc_time`. Layout: octets 0-3 = seconds since
+/// the 1970-01-01 UTC epoch (big-endian), octets 4-6 = fraction of a second
+/// as a 24-bit fixed-point value, octet 7 = TimeQuality (leap-seconds-known
+/// and clock-failure always clear here; clock-not-synchronized set when
+/// `synchronized` is false; bits 4-0 = `quality_accuracy`, the number of
+/// significant fraction bits, 0-24).
+pub fn encode_utc_time(quality_accuracy: u8, synchronized: bool) -> [u8; 8] {
+    let ts = get_realtime_clock().unwrap_or(timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    });
+    encode_utc_time_from(ts.tv_sec as i64, ts.tv_nsec as i64, quality_accuracy, synchronized)
+}
+
+fn encode_utc_time_from(epoch_seconds: i64, nanos: i64, quality_accuracy: u8, synchronized: bool) -> [u8; 8] {
+    let mut out = [0u8; 8];
+    out[0..4].copy_from_slice(&(epoch_seconds as u32).to_be_bytes());
+
+    // fraction * 2^24, i.e. ns -> (ns * 16_777_216 / 1_000_000_000)
+    let fraction_24 = ((nanos as u64 * 16_777_216) / 1_000_000_000) as u32;
+    let fraction_bytes = fraction_24.to_be_bytes();
+    out[4..7].copy_from_slice(&fraction_bytes[1..4]);
+
+    let mut quality = quality_accuracy & TIME_ACCURACY_MASK;
+    if !synchronized {
+        quality |= CLOCK_NOT_SYNCHRONIZED;
+    }
+    out[7] = quality;
+    out
+}
+
+/// Decoded form of an IEC 61850 UtcTime octet string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtcTime {
+    pub epoch_seconds: u32,
+    pub nanos: u32,
+    pub leap_seconds_known: bool,
+    pub clock_failure: bool,
+    pub synchronized: bool,
+    pub accuracy_bits: u8,
+}
+
+/// Decode an 8-octet IEC 61850 UtcTime produced by `encode_utc_time`.
+pub fn decode_utc_time(bytes: [u8; 8]) -> UtcTime {
+    let epoch_seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let fraction_24 = u32::from_be_bytes([0, bytes[4], bytes[5], bytes[6]]);
+    let nanos = ((fraction_24 as u64 * 1_000_000_000) / 16_777_216) as u32;
+    let quality = bytes[7];
+    UtcTime {
+        epoch_seconds,
+        nanos,
+        leap_seconds_known: quality & LEAP_SECONDS_KNOWN != 0,
+        clock_failure: quality & CLOCK_FAILURE != 0,
+        synchronized: quality & CLOCK_NOT_SYNCHRONIZED == 0,
+        accuracy_bits: quality & TIME_ACCURACY_MASK,
+    }
+}
+
 /// Sleep until an absolute time using CLOCK_MONOTONIC
 /// This is more accurate than relative sleep for periodic tasks
 pub fn sleep_until(wake_time: timespec) -> Result<()> {
@@ -133,40 +218,59 @@ pub fn timespec_diff_ns(start: &timespec, end: &timespec) -> i64 {
     sec_diff * 1_000_000_000 + nsec_diff
 }
 
-/// Complete real-time initialization for the current thread
-/// This combines all RT setup steps in the correct order
-///
-/// # Arguments
-/// * `core_id` - CPU core to pin this thread to
-/// * `priority` - SCHED_FIFO priority (1-99, higher = more important)
-///
-/// # Returns
-/// * `Ok(())` on success
-/// * `Err` with error description on failure
-pub fn init_realtime_thread(core_id: usize, priority: i32) -> Result<()> {
-    info!(
-        "Initializing real-time thread: core={}, priority={}",
-        core_id, priority
-    );
-
-    // Step 1: Lock memory first to prevent any paging
-    if let Err(e) = lock_memory() {
-        error!("Failed to lock memory: {}", e);
-        warn!("Continuing without memory locking (may cause latency spikes)");
-    }
-
-    // Step 2: Pre-fault stack to ensure pages are resident
-    prefault_stack(8 * 1024 * 1024); // 8MB stack
-
-    // Step 3: Pin to CPU core
-    pin_thread_to_core(core_id)?;
-
-    // Step 4: Set real-time priority (must be last)
-    if let Err(e) = set_realtime_priority(priority) {
-        error!("Failed to set RT priority: {}", e);
-        warn!("Continuing without RT priority (timing may not be deterministic)");
-    }
-
-    info!("Real-time thread initialization complete");
-    Ok(())
+/// Linux backend for [`RtPlatform`]: delegates directly to the syscalls
+/// above, so `init_realtime_thread` keeps its original SCHED_FIFO/mlockall/
+/// affinity behavior via the trait's default sequencing.
+pub struct LinuxRt;
+
+impl RtPlatform for LinuxRt {
+    fn pin_thread_to_core(&self, core_id: usize) -> Result<()> {
+        pin_thread_to_core(core_id)
+    }
+
+    fn set_realtime_priority(&self, priority: i32) -> Result<()> {
+        set_realtime_priority(priority)
+    }
+
+    fn lock_memory(&self) -> Result<()> {
+        lock_memory()
+    }
+
+    fn prefault_stack(&self, size_bytes: usize) {
+        prefault_stack(size_bytes)
+    }
+
+    fn sleep_for(&self, duration: Duration) -> Result<()> {
+        let mut wake_time = get_monotonic_time()?;
+        timespec_add_ns(&mut wake_time, duration.as_nanos() as i64);
+        sleep_until(wake_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_utc_time_round_trip() {
+        let encoded = encode_utc_time_from(1_700_000_000, 500_000_000, 24, true);
+        let decoded = decode_utc_time(encoded);
+
+        assert_eq!(decoded.epoch_seconds, 1_700_000_000);
+        // 24-bit fixed point rounds most fractions (0.5s happens to be exact); allow for rounding error.
+        assert!((decoded.nanos as i64 - 500_000_000).abs() < 100);
+        assert_eq!(decoded.accuracy_bits, 24);
+        assert!(decoded.synchronized);
+        assert!(!decoded.clock_failure);
+        assert!(!decoded.leap_seconds_known);
+    }
+
+    #[test]
+    fn test_encode_utc_time_sets_not_synchronized_bit() {
+        let encoded = encode_utc_time_from(0, 0, 10, false);
+        let decoded = decode_utc_time(encoded);
+
+        assert!(!decoded.synchronized);
+        assert_eq!(decoded.accuracy_bits, 10);
+    }
 }
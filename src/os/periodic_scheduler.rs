@@ -0,0 +1,198 @@
+//! Cyclic scheduler built on the `CLOCK_MONOTONIC` primitives in `linux_rt`,
+//! turning `sleep_until`/`timespec_add_ns` into a measurable periodic loop:
+//! every `tick()` sleeps to the planned wake time, records how far off the
+//! actual wakeup was, and advances the schedule - counting a deadline miss
+//! ("overrun") instead of busy-spinning through a backlog if the thread falls
+//! more than one period behind.
+
+use crate::os::linux_rt::{get_monotonic_time, sleep_until, timespec_add_ns, timespec_diff_ns};
+use anyhow::Result;
+use libc::timespec;
+
+/// One bucket per bit-length of the jitter magnitude, plus bucket 0 for
+/// exactly-zero jitter: bucket `k` (k>0) covers `[2^(k-1), 2^k)` nanoseconds.
+const HISTOGRAM_BUCKETS: usize = 65;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct JitterBucket {
+    count: u64,
+    min_ns: i64,
+    max_ns: i64,
+}
+
+fn bucket_for(jitter_ns: i64) -> usize {
+    let magnitude = jitter_ns.unsigned_abs();
+    if magnitude == 0 {
+        0
+    } else {
+        (64 - magnitude.leading_zeros()) as usize
+    }
+}
+
+/// Coarse log2-bucketed histogram of scheduling jitter (signed nanoseconds,
+/// `actual_wake - planned_wake`), like a coarse HdrHistogram: cheap enough to
+/// update on every wakeup of a SCHED_FIFO thread, at the cost of only
+/// approximate percentiles.
+struct JitterHistogram {
+    buckets: [JitterBucket; HISTOGRAM_BUCKETS],
+    total_samples: u64,
+}
+
+impl JitterHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: [JitterBucket::default(); HISTOGRAM_BUCKETS],
+            total_samples: 0,
+        }
+    }
+
+    fn record(&mut self, jitter_ns: i64) {
+        let bucket = &mut self.buckets[bucket_for(jitter_ns)];
+        if bucket.count == 0 {
+            bucket.min_ns = jitter_ns;
+            bucket.max_ns = jitter_ns;
+        } else {
+            bucket.min_ns = bucket.min_ns.min(jitter_ns);
+            bucket.max_ns = bucket.max_ns.max(jitter_ns);
+        }
+        bucket.count += 1;
+        self.total_samples += 1;
+    }
+
+    /// `(min_ns, max_ns, p99_ns)` across all recorded samples, or `None` if
+    /// nothing has been recorded yet. `p99_ns` is the max of the bucket
+    /// containing the 99th-percentile sample, not an exact order statistic.
+    fn summary(&self) -> Option<(i64, i64, i64)> {
+        if self.total_samples == 0 {
+            return None;
+        }
+        let min_ns = self.buckets.iter().filter(|b| b.count > 0).map(|b| b.min_ns).min()?;
+        let max_ns = self.buckets.iter().filter(|b| b.count > 0).map(|b| b.max_ns).max()?;
+
+        let target = ((self.total_samples as f64) * 0.99).ceil() as u64;
+        let mut cumulative = 0u64;
+        let mut p99_ns = max_ns;
+        for bucket in self.buckets.iter().filter(|b| b.count > 0) {
+            cumulative += bucket.count;
+            if cumulative >= target {
+                p99_ns = bucket.max_ns;
+                break;
+            }
+        }
+        Some((min_ns, max_ns, p99_ns))
+    }
+}
+
+/// Jitter/overrun summary returned by [`PeriodicScheduler::report`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PeriodicSchedulerReport {
+    pub min_jitter_ns: i64,
+    pub max_jitter_ns: i64,
+    pub p99_jitter_ns: i64,
+    pub overruns: u64,
+}
+
+/// Drives a periodic real-time loop against `CLOCK_MONOTONIC`, tracking
+/// deadline-miss ("overrun") counts and a jitter histogram alongside it.
+pub struct PeriodicScheduler {
+    period_ns: i64,
+    next_wake: timespec,
+    overruns: u64,
+    histogram: JitterHistogram,
+}
+
+impl PeriodicScheduler {
+    /// Create a scheduler for a period of `period_ns` nanoseconds, with the
+    /// first wake planned at the current monotonic time.
+    pub fn new(period_ns: i64) -> Result<Self> {
+        Ok(Self {
+            period_ns,
+            next_wake: get_monotonic_time()?,
+            overruns: 0,
+            histogram: JitterHistogram::new(),
+        })
+    }
+
+    /// Sleep until the next planned wake time, record the jitter between
+    /// planned and actual wake, then advance the schedule one period. If the
+    /// thread is already more than one period behind by the time it wakes,
+    /// counts an overrun and resynchronizes to `now + period` instead of
+    /// catching up through the backlog of missed wakeups.
+    pub fn tick(&mut self) -> Result<()> {
+        let planned_wake = self.next_wake;
+        sleep_until(planned_wake)?;
+
+        let now = get_monotonic_time()?;
+        let jitter_ns = timespec_diff_ns(&planned_wake, &now);
+        self.histogram.record(jitter_ns);
+
+        let mut wake = planned_wake;
+        timespec_add_ns(&mut wake, self.period_ns);
+
+        if timespec_diff_ns(&wake, &now) > self.period_ns {
+            self.overruns += 1;
+            wake = now;
+            timespec_add_ns(&mut wake, self.period_ns);
+        }
+
+        self.next_wake = wake;
+        Ok(())
+    }
+
+    /// Current min/max/p99 jitter and total overrun count.
+    pub fn report(&self) -> PeriodicSchedulerReport {
+        let (min_jitter_ns, max_jitter_ns, p99_jitter_ns) = self.histogram.summary().unwrap_or((0, 0, 0));
+        PeriodicSchedulerReport {
+            min_jitter_ns,
+            max_jitter_ns,
+            p99_jitter_ns,
+            overruns: self.overruns,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_for_boundaries() {
+        assert_eq!(bucket_for(0), 0);
+        assert_eq!(bucket_for(1), 1);
+        assert_eq!(bucket_for(-1), 1);
+        assert_eq!(bucket_for(2), 2);
+        assert_eq!(bucket_for(3), 2);
+        assert_eq!(bucket_for(4), 3);
+    }
+
+    #[test]
+    fn test_histogram_tracks_min_max_and_p99() {
+        let mut histogram = JitterHistogram::new();
+        for _ in 0..99 {
+            histogram.record(100);
+        }
+        histogram.record(1_000_000);
+
+        let (min_ns, max_ns, p99_ns) = histogram.summary().expect("histogram should have samples");
+        assert_eq!(min_ns, 100);
+        assert_eq!(max_ns, 1_000_000);
+        // The 99th of 100 samples still falls in the "100ns" bucket, not the outlier.
+        assert!(p99_ns < 1_000_000);
+    }
+
+    #[test]
+    fn test_histogram_empty_summary_is_none() {
+        assert!(JitterHistogram::new().summary().is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_periodic_scheduler_ticks_and_reports() {
+        let mut scheduler = PeriodicScheduler::new(1_000_000).expect("scheduler should init"); // 1ms period
+        for _ in 0..5 {
+            scheduler.tick().expect("tick should succeed");
+        }
+        let report = scheduler.report();
+        assert!(report.max_jitter_ns >= report.min_jitter_ns);
+    }
+}
@@ -0,0 +1,77 @@
+//! Cross-platform backend for real-time thread setup.
+//!
+//! Mirrors the approach the Rust standard library uses for its platform `sys`
+//! backends (teeos/sgx/hermit/itron/unsupported, selected via `cfg`): this
+//! trait captures the operations a real-time thread needs, `linux_rt::LinuxRt`
+//! backs it with the current SCHED_FIFO/mlockall/affinity syscalls, and
+//! `unsupported_rt::UnsupportedRt` is a no-op fallback so contributors can
+//! build and run the GOOSE pipeline on macOS/Windows in a "best effort",
+//! non-deterministic mode. Production builds keep the Linux path.
+
+use anyhow::Result;
+use std::time::Duration;
+
+/// Real-time thread setup operations, backed by a platform-specific implementation.
+pub trait RtPlatform {
+    /// Pin the current thread to a specific CPU core.
+    fn pin_thread_to_core(&self, core_id: usize) -> Result<()>;
+
+    /// Set real-time SCHED_FIFO priority (1-99, higher = more priority).
+    fn set_realtime_priority(&self, priority: i32) -> Result<()>;
+
+    /// Lock all current and future memory pages to prevent swapping.
+    fn lock_memory(&self) -> Result<()>;
+
+    /// Pre-fault stack memory so pages are resident before the RT section starts.
+    fn prefault_stack(&self, size_bytes: usize);
+
+    /// Sleep for `duration`, as precisely as this backend can manage.
+    fn sleep_for(&self, duration: Duration) -> Result<()>;
+
+    /// Run the full real-time initialization sequence for the current thread:
+    /// lock memory, pre-fault the stack, pin to a core, then raise priority.
+    ///
+    /// # Arguments
+    /// * `core_id` - CPU core to pin this thread to
+    /// * `priority` - SCHED_FIFO priority (1-99, higher = more important)
+    fn init_realtime_thread(&self, core_id: usize, priority: i32) -> Result<()> {
+        use log::{error, info, warn};
+
+        info!(
+            "Initializing real-time thread: core={}, priority={}",
+            core_id, priority
+        );
+
+        // Step 1: Lock memory first to prevent any paging
+        if let Err(e) = self.lock_memory() {
+            error!("Failed to lock memory: {}", e);
+            warn!("Continuing without memory locking (may cause latency spikes)");
+        }
+
+        // Step 2: Pre-fault stack to ensure pages are resident
+        self.prefault_stack(8 * 1024 * 1024); // 8MB stack
+
+        // Step 3: Pin to CPU core
+        self.pin_thread_to_core(core_id)?;
+
+        // Step 4: Set real-time priority (must be last)
+        if let Err(e) = self.set_realtime_priority(priority) {
+            error!("Failed to set RT priority: {}", e);
+            warn!("Continuing without RT priority (timing may not be deterministic)");
+        }
+
+        info!("Real-time thread initialization complete");
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use crate::os::linux_rt::LinuxRt as SelectedRtPlatform;
+#[cfg(not(target_os = "linux"))]
+pub use crate::os::unsupported_rt::UnsupportedRt as SelectedRtPlatform;
+
+/// The `RtPlatform` backend selected for this build (`LinuxRt` on Linux,
+/// `UnsupportedRt` everywhere else).
+pub fn platform() -> SelectedRtPlatform {
+    SelectedRtPlatform
+}
@@ -0,0 +1,46 @@
+//! Fallback [`RtPlatform`](super::rt_platform::RtPlatform) backend for
+//! platforms without the Linux real-time syscalls (`SCHED_FIFO`, `mlockall`,
+//! CPU affinity). Selected automatically by `rt_platform` on any non-Linux
+//! target, so the GOOSE pipeline still runs end-to-end on a contributor's
+//! laptop - just without deterministic scheduling.
+
+use super::rt_platform::RtPlatform;
+use anyhow::Result;
+use log::warn;
+use std::time::Duration;
+
+/// No-op real-time backend: every operation logs a warning and succeeds
+/// instead of touching syscalls this platform doesn't have.
+pub struct UnsupportedRt;
+
+impl RtPlatform for UnsupportedRt {
+    fn pin_thread_to_core(&self, core_id: usize) -> Result<()> {
+        warn!(
+            "CPU affinity is not supported on this platform, ignoring request to pin to core {}",
+            core_id
+        );
+        Ok(())
+    }
+
+    fn set_realtime_priority(&self, priority: i32) -> Result<()> {
+        warn!(
+            "Real-time scheduling is not supported on this platform, ignoring priority {}",
+            priority
+        );
+        Ok(())
+    }
+
+    fn lock_memory(&self) -> Result<()> {
+        warn!("Memory locking is not supported on this platform, running with normal paging");
+        Ok(())
+    }
+
+    fn prefault_stack(&self, _size_bytes: usize) {
+        warn!("Stack pre-faulting is not supported on this platform, skipping");
+    }
+
+    fn sleep_for(&self, duration: Duration) -> Result<()> {
+        std::thread::sleep(duration);
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+//! Per-PCS runtime-overridable values that `publisher::update_goose_frame_data`
+//! reads instead of the fixed placeholders it used to hardcode (SOC, status,
+//! and the charge/discharge/capacitive/inductive power limits). Mutated by
+//! `threads::control_server`'s `set`/`inject_fault` commands, so injecting a
+//! live measurement or fault flows straight into the published GOOSE frame
+//! and its stNum/event retransmission path the next time it's published.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// One PCS's currently live-overridable values.
+#[derive(Debug, Clone, Copy)]
+pub struct PcsLiveValues {
+    pub soc: f32,
+    pub status: i32,
+    pub max_charging_power: f32,
+    pub max_discharging_power: f32,
+    pub max_capacitive_power: f32,
+    pub max_inductive_power: f32,
+}
+
+/// Matches the placeholders `update_goose_frame_data` used to hardcode, so a
+/// PCS with no `set`/`inject_fault` commands yet publishes exactly the values
+/// it always did.
+impl Default for PcsLiveValues {
+    fn default() -> Self {
+        Self {
+            soc: 50.0,
+            status: 2, // Standby
+            max_charging_power: 1000.0,
+            max_discharging_power: 1000.0,
+            max_capacitive_power: 500.0,
+            max_inductive_power: 500.0,
+        }
+    }
+}
+
+/// Status code `inject_fault` forces a PCS into. There's no dedicated
+/// fault-code table in this simulator yet, so an injected fault is surfaced
+/// the same blunt way a real IED trips out of Standby/Running; the fault
+/// name itself is kept alongside for `get` to report.
+pub const FAULT_STATUS_CODE: i32 = 3;
+
+#[derive(Debug, Clone, Default)]
+struct PcsLiveEntry {
+    values: PcsLiveValues,
+    active_fault: Option<String>,
+}
+
+/// Shared store of [`PcsLiveValues`] keyed by logical ID, handed to both the
+/// publishing loop (via `values_or_default`) and `threads::control_server`.
+/// An ID only appears in the store once a `set` or `inject_fault` command has
+/// targeted it - `get_known` reflects that distinction so the control server
+/// can tell a real "no values recorded yet" apart from a PCS that just
+/// happens to sit at its defaults.
+#[derive(Debug, Clone, Default)]
+pub struct PcsLiveStore {
+    inner: Arc<Mutex<HashMap<u16, PcsLiveEntry>>>,
+}
+
+impl PcsLiveStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current values for `logical_id`, defaulting if nothing has been set
+    /// for it yet. This is what `update_goose_frame_data` should call - every
+    /// PCS has values to publish whether or not it's ever been touched by a
+    /// control command.
+    pub fn values_or_default(&self, logical_id: u16) -> PcsLiveValues {
+        self.lock().get(&logical_id).map(|entry| entry.values).unwrap_or_default()
+    }
+
+    /// Current values for `logical_id`, or `None` if no `set`/`inject_fault`
+    /// command has ever targeted it. Used by the control server's `get`
+    /// command to report an unknown ID as an error instead of silently
+    /// returning defaults.
+    pub fn get_known(&self, logical_id: u16) -> Option<PcsLiveValues> {
+        self.lock().get(&logical_id).map(|entry| entry.values)
+    }
+
+    /// Currently injected fault name for `logical_id`, if any.
+    pub fn active_fault(&self, logical_id: u16) -> Option<String> {
+        self.lock().get(&logical_id).and_then(|entry| entry.active_fault.clone())
+    }
+
+    /// Set one named field (`soc`, `status`, `max_charging_power`,
+    /// `max_discharging_power`, `max_capacitive_power`, `max_inductive_power`)
+    /// to `value`, creating an entry for `logical_id` if this is its first
+    /// command. Returns an error for an unrecognized field name.
+    pub fn set_field(&self, logical_id: u16, field: &str, value: f32) -> Result<(), String> {
+        let mut guard = self.lock();
+        let entry = guard.entry(logical_id).or_default();
+        match field {
+            "soc" => entry.values.soc = value,
+            "status" => entry.values.status = value as i32,
+            "max_charging_power" => entry.values.max_charging_power = value,
+            "max_discharging_power" => entry.values.max_discharging_power = value,
+            "max_capacitive_power" => entry.values.max_capacitive_power = value,
+            "max_inductive_power" => entry.values.max_inductive_power = value,
+            other => return Err(format!("unknown field '{}'", other)),
+        }
+        Ok(())
+    }
+
+    /// Record `fault_name` as the active fault for `logical_id` and force its
+    /// status to [`FAULT_STATUS_CODE`], creating an entry if needed.
+    pub fn inject_fault(&self, logical_id: u16, fault_name: &str) {
+        let mut guard = self.lock();
+        let entry = guard.entry(logical_id).or_default();
+        entry.active_fault = Some(fault_name.to_string());
+        entry.values.status = FAULT_STATUS_CODE;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u16, PcsLiveEntry>> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values_or_default_returns_defaults_for_untouched_id() {
+        let store = PcsLiveStore::new();
+        let values = store.values_or_default(7);
+        assert_eq!(values.soc, 50.0);
+        assert_eq!(values.status, 2);
+    }
+
+    #[test]
+    fn test_get_known_is_none_until_a_command_targets_the_id() {
+        let store = PcsLiveStore::new();
+        assert!(store.get_known(7).is_none());
+        store.set_field(7, "soc", 42.5).unwrap();
+        assert!(store.get_known(7).is_some());
+    }
+
+    #[test]
+    fn test_set_field_updates_requested_field_only() {
+        let store = PcsLiveStore::new();
+        store.set_field(1, "soc", 42.5).unwrap();
+        let values = store.values_or_default(1);
+        assert_eq!(values.soc, 42.5);
+        assert_eq!(values.max_charging_power, 1000.0);
+    }
+
+    #[test]
+    fn test_set_field_rejects_unknown_field() {
+        let store = PcsLiveStore::new();
+        let result = store.set_field(1, "bogus_field", 1.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_inject_fault_forces_fault_status_and_records_name() {
+        let store = PcsLiveStore::new();
+        store.inject_fault(3, "overvoltage");
+        assert_eq!(store.values_or_default(3).status, FAULT_STATUS_CODE);
+        assert_eq!(store.active_fault(3), Some("overvoltage".to_string()));
+    }
+}
@@ -4,15 +4,21 @@
 pub mod nameplate;
 pub mod types;
 pub mod publisher;
+pub mod live_values;
+pub mod scl_import;
 
 // Re-export main types
 pub use nameplate::NameplateConfig;
-pub use types::{PublisherPcsData};
-pub use publisher::{load_pcs_type_mappings, init_goose_frame_for_pcs, GooseFrame, PcsTypeMapping};
+pub use types::{AppIdIndex, PublisherPcsData, SubscriberPCSData};
+pub use publisher::{load_pcs_type_mappings, init_goose_frame_for_pcs, GooseFrame, GoosePublishState, PcsTypeMapping};
+pub use live_values::{PcsLiveStore, PcsLiveValues};
+pub use scl_import::{import_scl_type_mappings, GseControlParams, ScGooseControl};
 
 // Prelude for convenient imports
 pub mod prelude {
 pub use super::NameplateConfig;
-pub use super::{PublisherPcsData};
-pub use super::{GooseFrame, PcsTypeMapping};
+pub use super::{AppIdIndex, PublisherPcsData, SubscriberPCSData};
+pub use super::{GooseFrame, GoosePublishState, PcsTypeMapping};
+pub use super::{PcsLiveStore, PcsLiveValues};
+pub use super::{GseControlParams, ScGooseControl};
 }
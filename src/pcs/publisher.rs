@@ -2,7 +2,9 @@
 // Each PCS has its own GOOSE frame based on nameplate configuration
 // Each PCS type has different allData field mappings from PCS_publisher_alldata_mapping.json
 
+use crate::goose::pdu::getTimeMs;
 use crate::goose::types::{EthernetHeader, IECData, IECGoosePdu};
+use crate::pcs::live_values::PcsLiveStore;
 use crate::pcs::{NameplateConfig, PublisherPcsData};
 use anyhow::{Context, Result};
 use log::{info, warn};
@@ -11,10 +13,87 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
+use std::time::{Duration, Instant};
 
 /// Type alias for GOOSE frame (Ethernet header + GOOSE PDU)
 pub type GooseFrame = (EthernetHeader, IECGoosePdu);
 
+/// IEC 61850 GOOSE retransmission curve for one PCS type, since real IEDs vary
+/// it by device rather than sharing one fixed backoff.
+///
+/// * `t_min_ms` - T1, the shortest retransmission time sent right after a state change
+/// * `t0_ms` - T0, the stable-state retransmission time the curve settles at
+/// * `t_max_ms` - upper clamp for the growing interval (typically equal to `t0_ms`)
+/// * `growth` - multiplicative factor applied to the interval on each timeout-driven retransmit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RetransmissionProfile {
+    pub t0_ms: u64,
+    pub t_min_ms: u64,
+    pub t_max_ms: u64,
+    pub growth: f64,
+}
+
+impl Default for RetransmissionProfile {
+    /// Matches the previous hard-coded behavior: 2ms -> 4ms -> ... -> 5000ms
+    fn default() -> Self {
+        Self {
+            t0_ms: 5000,
+            t_min_ms: 2,
+            t_max_ms: 5000,
+            growth: 2.0,
+        }
+    }
+}
+
+impl RetransmissionProfile {
+    /// Grow `current_interval_ms` for the next timeout-driven retransmit, clamped to `t_max_ms`.
+    pub fn next_interval_ms(&self, current_interval_ms: u64) -> u64 {
+        let grown = (current_interval_ms as f64 * self.growth).round() as u64;
+        grown.clamp(self.t_min_ms, self.t_max_ms.max(self.t_min_ms))
+    }
+
+    /// `timeAllowedToLive` for `current_interval_ms`: ~2x the currently active interval,
+    /// so it grows alongside the interval instead of being left at a fixed default.
+    pub fn time_allowed_to_live_ms(&self, current_interval_ms: u64) -> u32 {
+        current_interval_ms.saturating_mul(2).min(u32::MAX as u64) as u32
+    }
+}
+
+/// Per-PCS retransmission/change-detection state, held alongside its
+/// `GooseFrame` in `PublisherPcsData.pcs_mapping`. Plays the same role as
+/// `threads::retransmit::RetransmitFrame`'s embedded timing fields, split out
+/// here since a `GooseFrame` is a plain `(EthernetHeader, IECGoosePdu)` tuple
+/// with nowhere to carry scheduling state of its own.
+#[derive(Debug, Clone)]
+pub struct GoosePublishState {
+    /// Currently active retransmission interval, in milliseconds.
+    current_interval_ms: u64,
+    /// Next wall-clock time this PCS's frame is due to be (re)published.
+    next_due: Instant,
+    /// `allData` values as of the last publish, compared against freshly
+    /// computed values to detect a real state change.
+    last_values: Vec<IECData>,
+}
+
+impl GoosePublishState {
+    /// Starts at T1 (shortest interval) with an empty `last_values`, so the
+    /// very first `update_goose_frame_data` call is always treated as a
+    /// change and bumps `stNum` from its `init_goose_frame_for_pcs` baseline.
+    pub fn new(type_mapping: &PcsTypeMapping) -> Self {
+        Self {
+            current_interval_ms: type_mapping.retransmission_profile.t_min_ms,
+            next_due: Instant::now(),
+            last_values: Vec::new(),
+        }
+    }
+
+    /// Next wall-clock time this PCS's frame is due to be (re)published, so
+    /// the publishing loop can sleep precisely instead of busy-polling.
+    pub fn next_publish_deadline(&self) -> Instant {
+        self.next_due
+    }
+}
+
 /// Mapping configuration for PCS type-specific allData fields
 /// Fields are stored as a Vec to preserve the exact order from JSON
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +102,9 @@ pub struct PcsTypeMapping {
     /// Ordered list of (field_name, data_type) - order matches JSON and GOOSE frame positions
     #[serde(skip)]
     pub fields: Vec<(String, String)>,
+    /// Per-type retransmission curve; defaults to the legacy 2ms->5000ms backoff
+    /// when the JSON mapping doesn't specify a `retransmission_profile` object.
+    pub retransmission_profile: RetransmissionProfile,
 }
 
 /// Load PCS type mappings from JSON file
@@ -49,23 +131,32 @@ pub fn load_pcs_type_mappings(path: &str) -> Result<HashMap<String, PcsTypeMappi
             .ok_or_else(|| anyhow::anyhow!("Missing or invalid 'pcstype' field"))?
             .to_string();
         
+        // Optional per-type retransmission curve; falls back to the legacy
+        // 2ms->5000ms backoff if this type's mapping doesn't specify one.
+        let retransmission_profile = match obj.get("retransmission_profile") {
+            Some(value) => serde_json::from_value(value.clone())
+                .with_context(|| format!("Invalid retransmission_profile for PCS type '{}'", pcs_type))?,
+            None => RetransmissionProfile::default(),
+        };
+
         // Build ordered field list from JSON object, preserving insertion order
         // serde_json::Map preserves the order from the JSON file
         let mut fields = Vec::new();
         for (field_name, value) in obj.iter() {
-            if field_name == "pcstype" {
-                continue; // Skip the pcstype field itself
+            if field_name == "pcstype" || field_name == "retransmission_profile" {
+                continue; // Skip non-allData-field keys
             }
             let data_type = value.as_str()
                 .ok_or_else(|| anyhow::anyhow!("Field '{}' has non-string value", field_name))?
                 .to_string();
             fields.push((field_name.clone(), data_type));
         }
-        
+
         let field_count = fields.len();
         let mapping = PcsTypeMapping {
             pcstype: pcs_type.clone(),
             fields,
+            retransmission_profile,
         };
         
         result.insert(pcs_type.clone(), mapping);
@@ -137,7 +228,11 @@ pub fn init_goose_frame_for_pcs(
     // Create GOOSE PDU
     let mut goose_pdu = IECGoosePdu::default();
     goose_pdu.gocbRef = gocb_ref.clone();
-    goose_pdu.timeAllowedtoLive = 5000; // Default 5 seconds
+    // Start at T1 (shortest interval), matching the retransmit thread's
+    // initial interval for this PCS type's profile.
+    goose_pdu.timeAllowedtoLive = type_mapping
+        .retransmission_profile
+        .time_allowed_to_live_ms(type_mapping.retransmission_profile.t_min_ms);
     goose_pdu.datSet = data_set.clone();
     goose_pdu.goID = go_id.clone();
     goose_pdu.t = [0; 8]; // Will be updated when publishing
@@ -147,77 +242,120 @@ pub fn init_goose_frame_for_pcs(
     goose_pdu.confRev = conf_rev;
     goose_pdu.ndsCom = nds_com;
     
-    // Initialize allData based on type mapping
-    // Fields are already in correct order from JSON (Vec preserves order)
-    goose_pdu.numDatSetEntries = type_mapping.fields.len() as u32;
-    
-    // Initialize allData with default values in the exact order from JSON
-    // This order matches the GOOSE frame structure where position matters
+    // Initialize allData with default values in the exact order from JSON.
+    // This order matches the GOOSE frame structure where position matters.
+    // `numDatSetEntries` is set from the actual push count below (not
+    // `type_mapping.fields.len()`), so an unrecognized type name can't leave
+    // it disagreeing with the real `allData` length and desyncing the frame.
     for (field_name, data_type) in &type_mapping.fields {
         match data_type.as_str() {
             "boolean" => goose_pdu.allData.push(IECData::boolean(false)),
             "float" => goose_pdu.allData.push(IECData::float32(0.0)),
             "int" => goose_pdu.allData.push(IECData::int32(0)),
+            // CODED ENUM: a coded integer for status/mode fields.
+            "enum" => goose_pdu.allData.push(IECData::int32(0)),
+            // Quality: IEC 61850-7-3 13-bit bitstring, padded to 2 bytes.
+            "bitstring" => goose_pdu.allData.push(IECData::bit_string { padding: 3, val: vec![0, 0] }),
+            "unsigned" => goose_pdu.allData.push(IECData::int32u(0)),
+            "utctime" => goose_pdu.allData.push(IECData::utc_time([0; 8])),
+            "visible-string" => goose_pdu.allData.push(IECData::visible_string(String::new())),
             _ => warn!("Unknown data type '{}' for field '{}'", data_type, field_name),
         }
     }
-    
+    goose_pdu.numDatSetEntries = goose_pdu.allData.len() as u32;
+
     info!("Initialized GOOSE frame for PCS logical_id {:?}, type {}, {} fields in JSON order",
         nameplate.logical_id, type_mapping.pcstype, goose_pdu.allData.len());
     
     Ok((eth_header, goose_pdu))
 }
 
-/// Update GOOSE frame allData with current PCS data
+/// Recompute `frame`'s `allData` from `pcs_data` and `live_values` (SOC,
+/// status, and the charge/discharge/capacitive/inductive power limits,
+/// previously hardcoded placeholders - now whatever `threads::control_server`
+/// last set for `logical_id`, or the same defaults as before if nothing has),
+/// and drive the IEC 61850 retransmission curve in `state`: if any value
+/// actually changed since the last publish, `stNum` bumps, `sqNum` resets to
+/// 0, and the interval restarts at `t_min_ms`; otherwise this is a
+/// steady-state heartbeat - only `sqNum` advances, and the interval keeps
+/// growing toward `t0_ms` per `RetransmissionProfile::next_interval_ms`.
+/// `timeAllowedtoLive` and `state.next_publish_deadline()` are updated to
+/// match whichever interval is now active, the same policy
+/// `threads::retransmit` uses for the nameplate-driven publishers.
 pub fn update_goose_frame_data(
     frame: &mut GooseFrame,
+    state: &mut GoosePublishState,
+    logical_id: u16,
+    live_values: &PcsLiveStore,
     pcs_data: &PublisherPcsData,
     type_mapping: &PcsTypeMapping,
 ) -> Result<()> {
-    // Update allData values based on field mappings
+    let live = live_values.values_or_default(logical_id);
+
+    // Compute fresh allData values based on field mappings.
     // Fields are in correct order from JSON (Vec preserves order)
+    let mut new_values = frame.1.allData.clone();
     for (data_index, (field_name, _data_type)) in type_mapping.fields.iter().enumerate() {
-        if data_index >= frame.1.allData.len() {
+        if data_index >= new_values.len() {
             break;
         }
-        
+
         // Map field names to actual PCS data based on position in allData
         // Field order from JSON matches GOOSE frame structure
         match field_name.as_str() {
             name if name.contains("realtime_active_power") => {
                 let (active_power, _, _, _) = pcs_data.get_feedback_values();
-                frame.1.allData[data_index] = IECData::float32(active_power);
+                new_values[data_index] = IECData::float32(active_power);
             }
             name if name.contains("realtime_reactive_power") => {
                 let (_, reactive_power, _, _) = pcs_data.get_feedback_values();
-                frame.1.allData[data_index] = IECData::float32(reactive_power);
+                new_values[data_index] = IECData::float32(reactive_power);
             }
             name if name.contains("status") => {
-                // Default status - extend based on your PCSStatus enum
-                frame.1.allData[data_index] = IECData::int32(2); // Standby
+                new_values[data_index] = IECData::int32(live.status);
             }
             name if name.contains("soc") => {
-                // State of charge - placeholder
-                frame.1.allData[data_index] = IECData::float32(50.0);
+                new_values[data_index] = IECData::float32(live.soc);
             }
             name if name.contains("maximum_charging_power") => {
-                frame.1.allData[data_index] = IECData::float32(1000.0); // Placeholder
+                new_values[data_index] = IECData::float32(live.max_charging_power);
             }
             name if name.contains("maximum_discharging_power") => {
-                frame.1.allData[data_index] = IECData::float32(1000.0); // Placeholder
+                new_values[data_index] = IECData::float32(live.max_discharging_power);
             }
             name if name.contains("maximum_capacitive_power") => {
-                frame.1.allData[data_index] = IECData::float32(500.0); // Placeholder
+                new_values[data_index] = IECData::float32(live.max_capacitive_power);
             }
             name if name.contains("maximum_inductive_power") => {
-                frame.1.allData[data_index] = IECData::float32(500.0); // Placeholder
+                new_values[data_index] = IECData::float32(live.max_inductive_power);
             }
             _ => {
                 // Keep default values for spare fields
             }
         }
     }
-    
+
+    let profile = &type_mapping.retransmission_profile;
+    let changed = state.last_values != new_values;
+
+    if changed {
+        frame.1.stNum = frame.1.stNum.wrapping_add(1);
+        frame.1.sqNum = 0;
+        state.current_interval_ms = profile.t_min_ms;
+    } else {
+        frame.1.sqNum = frame.1.sqNum.wrapping_add(1);
+    }
+
+    frame.1.t = getTimeMs();
+    frame.1.timeAllowedtoLive = profile.time_allowed_to_live_ms(state.current_interval_ms);
+    frame.1.allData = new_values.clone();
+    state.last_values = new_values;
+    state.next_due = Instant::now() + Duration::from_millis(state.current_interval_ms);
+
+    if !changed {
+        state.current_interval_ms = profile.next_interval_ms(state.current_interval_ms);
+    }
+
     Ok(())
 }
 
@@ -295,4 +433,113 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0x8100);
     }
+
+    fn sample_type_mapping() -> PcsTypeMapping {
+        PcsTypeMapping {
+            pcstype: "type_a".to_string(),
+            fields: vec![("realtime_active_power".to_string(), "float".to_string())],
+            retransmission_profile: RetransmissionProfile::default(),
+        }
+    }
+
+    #[test]
+    fn test_goose_publish_state_new_starts_at_t_min_with_empty_last_values() {
+        let mapping = sample_type_mapping();
+        let state = GoosePublishState::new(&mapping);
+        assert_eq!(state.current_interval_ms, mapping.retransmission_profile.t_min_ms);
+        assert!(state.last_values.is_empty());
+    }
+
+    #[test]
+    fn test_goose_publish_state_next_publish_deadline_matches_next_due() {
+        let mapping = sample_type_mapping();
+        let state = GoosePublishState::new(&mapping);
+        assert_eq!(state.next_publish_deadline(), state.next_due);
+    }
+
+    fn sample_nameplate() -> NameplateConfig {
+        NameplateConfig {
+            row_number: Some(1),
+            device_id: Some("PCS1".to_string()),
+            goose_appid: Some(0x0008),
+            goose_src_addr: Some("e8-d8-d1-eb-cb-b6".to_string()),
+            goose_dst_addr: Some("01-0C-CD-01-00-08".to_string()),
+            goose_tpid: Some("0x8100".to_string()),
+            goose_tci: Some("0x8000".to_string()),
+            goose_gocb_ref: Some("XD11LDevice1/LLN0$GO$Go_Gcb2".to_string()),
+            goose_data_set: Some("XD11LDevice1/LLN0$dsGOOSE2".to_string()),
+            goose_go_id: Some("XD11LDevice1/LLN0.Go_Gcb2".to_string()),
+            goose_simulation: Some("false".to_string()),
+            goose_conf_rev: Some("5".to_string()),
+            goose_nds_com: Some("false".to_string()),
+            feed_line_id: None,
+            feed_line_alias: None,
+            logical_id: Some(1),
+            pcs_type: Some("type_a".to_string()),
+            pms_appid: None,
+        }
+    }
+
+    #[test]
+    fn test_init_goose_frame_for_pcs_supports_extended_data_types() {
+        let nameplate = sample_nameplate();
+        let mut mapping = sample_type_mapping();
+        mapping.fields = vec![
+            ("status".to_string(), "enum".to_string()),
+            ("quality".to_string(), "bitstring".to_string()),
+            ("runtime_hours".to_string(), "unsigned".to_string()),
+            ("last_update".to_string(), "utctime".to_string()),
+            ("serial_number".to_string(), "visible-string".to_string()),
+        ];
+
+        let (_, pdu) = init_goose_frame_for_pcs(&nameplate, &mapping).expect("should build frame");
+
+        assert_eq!(pdu.numDatSetEntries as usize, pdu.allData.len());
+        assert_eq!(pdu.allData.len(), mapping.fields.len());
+        assert!(matches!(pdu.allData[0], IECData::int32(0)));
+        assert!(matches!(pdu.allData[1], IECData::bit_string { padding: 3, .. }));
+        assert!(matches!(pdu.allData[2], IECData::int32u(0)));
+        assert!(matches!(pdu.allData[3], IECData::utc_time(_)));
+        assert!(matches!(pdu.allData[4], IECData::visible_string(ref s) if s.is_empty()));
+    }
+
+    #[test]
+    fn test_init_goose_frame_for_pcs_keeps_numdatsetentries_in_sync_with_unknown_type() {
+        let nameplate = sample_nameplate();
+        let mut mapping = sample_type_mapping();
+        mapping.fields = vec![
+            ("active_power".to_string(), "float".to_string()),
+            ("mystery_field".to_string(), "timestamp_v2".to_string()),
+        ];
+
+        let (_, pdu) = init_goose_frame_for_pcs(&nameplate, &mapping).expect("should build frame");
+
+        // The unknown type is skipped (with a warning), so allData only has
+        // one entry - numDatSetEntries must track that, not fields.len().
+        assert_eq!(pdu.allData.len(), 1);
+        assert_eq!(pdu.numDatSetEntries, 1);
+    }
+
+    #[test]
+    fn test_init_goose_frame_for_pcs_round_trips_through_ber_codec() {
+        use crate::goose::pdu::{decodeGooseFrame, encodeGooseFrame};
+
+        let nameplate = sample_nameplate();
+        let mapping = sample_type_mapping();
+        let (mut header, pdu) = init_goose_frame_for_pcs(&nameplate, &mapping).expect("should build frame");
+
+        let mut buf = [0u8; 512];
+        let size = encodeGooseFrame(&mut header, &pdu, &mut buf, 0);
+
+        let mut decoded_header = EthernetHeader::default();
+        let mut decoded_pdu = IECGoosePdu::default();
+        decodeGooseFrame(&mut decoded_header, &mut decoded_pdu, &buf[..size], 0).expect("should decode");
+
+        assert_eq!(decoded_header.srcAddr, header.srcAddr);
+        assert_eq!(decoded_header.dstAddr, header.dstAddr);
+        assert_eq!(decoded_pdu.goID, pdu.goID);
+        assert_eq!(decoded_pdu.gocbRef, pdu.gocbRef);
+        assert_eq!(decoded_pdu.confRev, pdu.confRev);
+        assert_eq!(decoded_pdu.allData, pdu.allData);
+    }
 }
@@ -0,0 +1,242 @@
+//! Import PCS dataset mappings from a Substation Configuration Language file
+//! (SCL/ICD, IEC 61850-6 XML) instead of authoring
+//! `PCS_publisher_alldata_mapping.json` by hand. Parses each `<GSEControl>`
+//! (gocbRef components, `datSet` reference, `confRev`, `appID`), the
+//! `<DataSet>` it points at, and that DataSet's ordered `<FCDA>` entries, so
+//! dataset order and the live published frame always agree with what the IED
+//! tooling actually exported.
+//!
+//! Scope: a field's type is read directly off the `<FCDA>` element's
+//! `bType` attribute when the exporting tool includes it (some ICD exports
+//! annotate FCDAs this way for readability). This importer does not walk the
+//! full `<DataTypeTemplates>` -> `<LNodeType>` -> `<DOType>` -> `<DAType>`
+//! chain a strictly conformant SCL parser would need to resolve a DA's basic
+//! type from first principles - a field whose type can't be determined this
+//! way falls back to `"float"` with a `warn!`, the same tolerant-default
+//! philosophy `load_pcs_type_mappings` already uses for its hand-authored JSON.
+
+use crate::pcs::publisher::{PcsTypeMapping, RetransmissionProfile};
+use anyhow::{Context, Result};
+use log::{info, warn};
+use roxmltree::Node;
+use std::collections::HashMap;
+use std::fs;
+
+/// GOOSE control-block parameters parsed out of one `<GSEControl>` element -
+/// the values `NameplateConfig` otherwise carries by hand
+/// (`goose_gocb_ref`/`goose_data_set`/`goose_conf_rev`/`goose_appid`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GseControlParams {
+    pub gocb_ref: String,
+    pub data_set: String,
+    pub conf_rev: u32,
+    pub appid: Option<u16>,
+}
+
+/// One SCL `<GSEControl>`'s resolved dataset mapping plus its control-block
+/// parameters, keyed by the control block's name in
+/// [`import_scl_type_mappings`]'s returned map.
+#[derive(Debug, Clone)]
+pub struct ScGooseControl {
+    pub control_params: GseControlParams,
+    pub type_mapping: PcsTypeMapping,
+}
+
+fn is_tag(node: &Node, name: &str) -> bool {
+    node.tag_name().name() == name
+}
+
+/// Parse `path` as an SCL/ICD XML file and return one [`ScGooseControl`] per
+/// `<GSEControl>` found, keyed by the control block's `name` attribute. A
+/// `<GSEControl>` that's missing required attributes, references a `DataSet`
+/// that can't be found under the same logical node, or whose dataset has no
+/// usable `FCDA` entries is skipped with a `warn!` rather than failing the
+/// whole import.
+pub fn import_scl_type_mappings(path: &str) -> Result<HashMap<String, ScGooseControl>> {
+    info!("Importing PCS dataset mappings from SCL file: {}", path);
+    let xml = fs::read_to_string(path).with_context(|| format!("Failed to read SCL file: {}", path))?;
+    let doc = roxmltree::Document::parse(&xml).with_context(|| format!("Failed to parse SCL XML: {}", path))?;
+
+    let mut result = HashMap::new();
+
+    for ln in doc.descendants().filter(|n| is_tag(n, "LN0") || is_tag(n, "LN")) {
+        let ld_inst = ln
+            .ancestors()
+            .find(|n| is_tag(n, "LDevice"))
+            .and_then(|n| n.attribute("inst"))
+            .unwrap_or("");
+        let ln_class = ln.attribute("lnClass").unwrap_or("LLN0");
+
+        for gse in ln.children().filter(|n| is_tag(n, "GSEControl")) {
+            let name = match gse.attribute("name") {
+                Some(n) => n.to_string(),
+                None => {
+                    warn!("SCL import: skipping GSEControl with no 'name' attribute");
+                    continue;
+                }
+            };
+            let data_set_name = match gse.attribute("datSet") {
+                Some(d) => d,
+                None => {
+                    warn!("SCL import: GSEControl '{}' has no 'datSet' attribute, skipping", name);
+                    continue;
+                }
+            };
+
+            let dataset = match ln.children().find(|n| is_tag(n, "DataSet") && n.attribute("name") == Some(data_set_name)) {
+                Some(ds) => ds,
+                None => {
+                    warn!(
+                        "SCL import: GSEControl '{}' references DataSet '{}' which was not found under the same logical node, skipping",
+                        name, data_set_name
+                    );
+                    continue;
+                }
+            };
+
+            let mut fields = Vec::new();
+            for fcda in dataset.children().filter(|n| is_tag(n, "FCDA")) {
+                let do_name = fcda.attribute("doName").unwrap_or("");
+                if do_name.is_empty() {
+                    warn!("SCL import: FCDA in DataSet '{}' has no 'doName', skipping field", data_set_name);
+                    continue;
+                }
+                let field_name = match fcda.attribute("daName") {
+                    Some(da_name) if !da_name.is_empty() => format!("{}.{}", do_name, da_name),
+                    _ => do_name.to_string(),
+                };
+                let data_type = match fcda.attribute("bType").and_then(map_sc_btype) {
+                    Some(mapped) => mapped,
+                    None => {
+                        warn!(
+                            "SCL import: could not resolve a data type for field '{}' in DataSet '{}', defaulting to 'float'",
+                            field_name, data_set_name
+                        );
+                        "float".to_string()
+                    }
+                };
+                fields.push((field_name, data_type));
+            }
+
+            if fields.is_empty() {
+                warn!("SCL import: DataSet '{}' for GSEControl '{}' has no usable FCDA entries, skipping", data_set_name, name);
+                continue;
+            }
+
+            let field_count = fields.len();
+            let conf_rev = gse.attribute("confRev").and_then(|v| v.parse().ok()).unwrap_or(1);
+            let appid = gse.attribute("appID").and_then(parse_appid);
+            let gocb_ref = format!("{}/{}$GO${}", ld_inst, ln_class, name);
+
+            let type_mapping = PcsTypeMapping {
+                pcstype: name.clone(),
+                fields,
+                retransmission_profile: RetransmissionProfile::default(),
+            };
+            let control_params = GseControlParams {
+                gocb_ref,
+                data_set: data_set_name.to_string(),
+                conf_rev,
+                appid,
+            };
+
+            info!(
+                "SCL import: loaded GSEControl '{}' ({} fields from DataSet '{}')",
+                name, field_count, data_set_name
+            );
+            result.insert(name, ScGooseControl { control_params, type_mapping });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Map an SCL `bType` name to the type-name vocabulary
+/// `load_pcs_type_mappings`/`init_goose_frame_for_pcs` already accept
+/// (`"boolean"`, `"float"`, `"int"`, `"unsigned"`, `"bitstring"`,
+/// `"utctime"`, `"enum"`, `"visible-string"`). Returns `None` for an
+/// unrecognized `bType`.
+fn map_sc_btype(btype: &str) -> Option<String> {
+    let mapped = match btype {
+        "BOOLEAN" => "boolean",
+        "FLOAT32" | "FLOAT64" => "float",
+        "INT8" | "INT16" | "INT32" | "INT64" => "int",
+        "INT8U" | "INT16U" | "INT32U" => "unsigned",
+        "Quality" | "Dbpos" => "bitstring",
+        "Timestamp" => "utctime",
+        "Enum" => "enum",
+        "VisString64" | "VisString129" | "VisString255" => "visible-string",
+        _ => return None,
+    };
+    Some(mapped.to_string())
+}
+
+/// Parse an `appID` attribute (hex, with or without `0x` prefix) to `u16`.
+fn parse_appid(s: &str) -> Option<u16> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    u16::from_str_radix(s, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const SAMPLE_SCL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<SCL xmlns="http://www.iec.ch/61850/2003/SCL">
+  <IED name="PCS1">
+    <AccessPoint name="AP1">
+      <Server>
+        <LDevice inst="LD0">
+          <LN0 lnClass="LLN0" inst="" lnType="LLN0Type">
+            <DataSet name="dsGOOSE2">
+              <FCDA doName="Pos" daName="stVal" fc="ST" bType="BOOLEAN"/>
+              <FCDA doName="TotW" daName="mag.f" fc="MX" bType="FLOAT32"/>
+              <FCDA doName="Health" daName="q" fc="ST" bType="Quality"/>
+            </DataSet>
+            <GSEControl name="Go_Gcb2" datSet="dsGOOSE2" confRev="5" appID="0x0008"/>
+          </LN0>
+        </LDevice>
+      </Server>
+    </AccessPoint>
+  </IED>
+</SCL>
+"#;
+
+    fn write_sample_scl() -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("scl_import_test_{}.icd", std::process::id()));
+        let mut file = std::fs::File::create(&path).expect("create temp SCL file");
+        file.write_all(SAMPLE_SCL.as_bytes()).expect("write temp SCL file");
+        path
+    }
+
+    #[test]
+    fn test_import_scl_type_mappings_resolves_control_and_fields() {
+        let path = write_sample_scl();
+        let result = import_scl_type_mappings(path.to_str().unwrap()).expect("should parse SCL");
+        let _ = std::fs::remove_file(&path);
+
+        let control = result.get("Go_Gcb2").expect("Go_Gcb2 GSEControl should be present");
+        assert_eq!(control.control_params.data_set, "dsGOOSE2");
+        assert_eq!(control.control_params.conf_rev, 5);
+        assert_eq!(control.control_params.appid, Some(0x0008));
+        assert_eq!(control.control_params.gocb_ref, "LD0/LLN0$GO$Go_Gcb2");
+
+        assert_eq!(control.type_mapping.fields.len(), 3);
+        assert_eq!(control.type_mapping.fields[0], ("Pos.stVal".to_string(), "boolean".to_string()));
+        assert_eq!(control.type_mapping.fields[1], ("TotW.mag.f".to_string(), "float".to_string()));
+        assert_eq!(control.type_mapping.fields[2], ("Health.q".to_string(), "bitstring".to_string()));
+    }
+
+    #[test]
+    fn test_map_sc_btype_unknown_returns_none() {
+        assert_eq!(map_sc_btype("SomeUnknownType"), None);
+    }
+
+    #[test]
+    fn test_import_scl_type_mappings_errors_on_missing_file() {
+        let result = import_scl_type_mappings("/nonexistent/path/to/file.icd");
+        assert!(result.is_err());
+    }
+}
@@ -2,18 +2,21 @@ use crate::goose::types::{EthernetHeader, IECData, IECGoosePdu};
 use anyhow::Result;
 use log::{info,warn};
 use std::collections::HashMap;
+use std::sync::Mutex;
 use crate::pcs::nameplate::NameplateConfig;
+use crate::pcs::publisher::GoosePublishState;
 use crate::pcs::{PcsTypeMapping, init_goose_frame_for_pcs, publisher};
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct PublisherPcsData {
     /// Nameplate/configuration values for this PCS (includes device id)
-    
-    pub pcs_mapping: HashMap<u16, Vec<(EthernetHeader, IECGoosePdu)>>, // logical ID and goose frame to be sent from this pcs.
+
+    // logical ID -> (goose frame, retransmission/change-detection state) to be sent from this pcs.
+    pub pcs_mapping: HashMap<u16, Vec<(EthernetHeader, IECGoosePdu, GoosePublishState)>>,
 }
 
 impl PublisherPcsData {
-    pub fn new(mut self,config:&Vec<NameplateConfig>,pcs_type:&PcsTypeMapping)  {
+    pub fn new(&mut self, config: &Vec<NameplateConfig>, pcs_type: &PcsTypeMapping) {
         for cfg in config {
            let gooseframe =init_goose_frame_for_pcs(cfg, pcs_type);
               match gooseframe {
@@ -23,15 +26,219 @@ impl PublisherPcsData {
                      // Also assuming that frame is of type (EthernetHeader, IECGoosePdu)
                      // You might need to adjust this based on actual types
                      // For demonstration, we use a placeholder logical ID
-                     let logical_id = cfg.logical_id.unwrap() as u16; 
-                     self.pcs_mapping.entry(logical_id).or_insert_with(Vec::new).push(frame);
+                     let logical_id = cfg.logical_id.unwrap() as u16;
+                     let state = GoosePublishState::new(pcs_type);
+                     self.pcs_mapping.entry(logical_id).or_insert_with(Vec::new).push((frame.0, frame.1, state));
                 },
                 Err(e) => {
                      warn!("Failed to initialize GOOSE frame for PCS with device ID {:?}: {}", cfg.logical_id, e);
                 }
               }
-        }   
-        
+        }
+
+    }
+
+}
+
+/// How much slack to give a publisher's advertised `timeAllowedToLive` before
+/// declaring its last frame stale, to tolerate normal network/scheduling jitter.
+pub const TAL_STALENESS_MULTIPLIER: u32 = 2;
+
+/// Subscriber-side state for a single PCS: the last GOOSE PDU received for it,
+/// plus the nameplate values `get_info` needs to fill in `StPCSinfo`.
+///
+/// Tracks liveness per IEC 61850: the `lan_id` and receive `Instant` of the most
+/// recent accepted frame, the decoded `timeAllowedToLive`, and `stNum`/`sqNum` so a
+/// silent publisher ages out instead of reporting its last values forever, and so
+/// `stNum` regressions (publisher restart) / `sqNum` gaps (dropped frames) are logged
+/// tagged with the LAN the frame arrived on. Frames from both LANs update this one
+/// record; liveness and sequence numbers are not kept separately per LAN.
+#[derive(Debug, Clone)]
+pub struct SubscriberPCSData {
+    pcs_id: u16,
+    nameplate_appid: Option<u16>,
+    nameplate_feed_line_id: Option<u16>,
+    goose_pdu: IECGoosePdu,
+    is_valid: bool,
+    last_rx: Option<(u16, std::time::Instant)>, // (lan_id, receive time) of the most recent accepted frame
+}
+
+impl Default for SubscriberPCSData {
+    fn default() -> Self {
+        Self {
+            pcs_id: 0,
+            nameplate_appid: None,
+            nameplate_feed_line_id: None,
+            goose_pdu: IECGoosePdu::default(),
+            is_valid: false,
+            last_rx: None,
+        }
+    }
+}
+
+impl SubscriberPCSData {
+    pub fn new(pcs_id: u16, nameplate_appid: Option<u16>, nameplate_feed_line_id: Option<u16>) -> Self {
+        Self {
+            pcs_id,
+            nameplate_appid,
+            nameplate_feed_line_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn pcs_id(&self) -> u16 {
+        self.pcs_id
+    }
+
+    pub fn nameplate_appid(&self) -> Option<u16> {
+        self.nameplate_appid
+    }
+
+    pub fn nameplate_feed_line_id(&self) -> Option<u16> {
+        self.nameplate_feed_line_id
+    }
+
+    /// Valid only while fresh: a frame has been received and `now - last_rx` is
+    /// within `TAL_STALENESS_MULTIPLIER * timeAllowedToLive`. A stale subscriber
+    /// is treated as invalid even though the last-known PDU is still held.
+    pub fn is_data_valid(&self) -> bool {
+        if !self.is_valid {
+            return false;
+        }
+        match self.last_rx {
+            Some((_, rx_time)) => !self.is_stale_at(rx_time),
+            None => false,
+        }
+    }
+
+    fn is_stale_at(&self, rx_time: std::time::Instant) -> bool {
+        let tal = std::time::Duration::from_millis(
+            (self.goose_pdu.timeAllowedtoLive as u64) * (TAL_STALENESS_MULTIPLIER as u64),
+        );
+        rx_time.elapsed() > tal
     }
 
+    /// Force this subscriber stale (e.g. from a periodic sweeper), so `get_info`
+    /// reports `is_valid = 0` and measurands as `INVALID_VALUE` on the next read.
+    pub fn mark_stale(&mut self) {
+        self.is_valid = false;
+    }
+
+    pub fn get_alldata(&self) -> &Vec<IECData> {
+        &self.goose_pdu.allData
+    }
+
+    /// Store the decoded GOOSE PDU received on `lan_id` as this PCS's latest data.
+    ///
+    /// Detects and logs an `stNum` regression (publisher restart) and an `sqNum`
+    /// gap (dropped frame) relative to the previously accepted frame before
+    /// overwriting it.
+    pub fn update_from_goose(&mut self, pdu: &IECGoosePdu, lan_id: u16) {
+        if pdu.stNum < self.goose_pdu.stNum {
+            warn!(
+                "PCS {} lan{}: stNum regressed {} -> {}, publisher restart suspected",
+                self.pcs_id, lan_id, self.goose_pdu.stNum, pdu.stNum
+            );
+        } else if pdu.stNum == self.goose_pdu.stNum && pdu.sqNum > self.goose_pdu.sqNum + 1 {
+            warn!(
+                "PCS {} lan{}: sqNum gap {} -> {}, {} frame(s) likely dropped",
+                self.pcs_id,
+                lan_id,
+                self.goose_pdu.sqNum,
+                pdu.sqNum,
+                pdu.sqNum - self.goose_pdu.sqNum - 1
+            );
+        }
+
+        self.goose_pdu = pdu.clone();
+        self.is_valid = true;
+        self.last_rx = Some((lan_id, std::time::Instant::now()));
+    }
+
+    /// Re-check staleness against the wall clock and flip to invalid if the
+    /// publisher has gone silent past `timeAllowedToLive * TAL_STALENESS_MULTIPLIER`.
+    /// Intended for use by a periodic sweeper alongside `get_info`'s own check.
+    pub fn sweep_staleness(&mut self) -> bool {
+        if let Some((_, rx_time)) = self.last_rx {
+            if self.is_valid && self.is_stale_at(rx_time) {
+                self.mark_stale();
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// O(1) APPID -> PCS id dispatch index.
+///
+/// `process_rx_packet` used to lock the whole `pcs_data_pool` map and scan every
+/// entry comparing `nameplate_appid()` against the frame's APPID. This index is
+/// built once when PCS entries are registered (and kept current on insert/remove)
+/// so a received frame looks up only the PCS ids that actually subscribe to its APPID.
+#[derive(Debug, Default)]
+pub struct AppIdIndex {
+    index: Mutex<HashMap<u16, Vec<u16>>>,
+}
+
+impl AppIdIndex {
+    pub fn new() -> Self {
+        Self {
+            index: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register `pcs_id` as a subscriber of `appid`.
+    pub fn register(&self, appid: u16, pcs_id: u16) {
+        let mut index = self.lock_index();
+        let ids = index.entry(appid).or_insert_with(Vec::new);
+        if !ids.contains(&pcs_id) {
+            ids.push(pcs_id);
+        }
+    }
+
+    /// Remove `pcs_id` from `appid`'s subscriber list (e.g. on APPID reassignment).
+    pub fn unregister(&self, appid: u16, pcs_id: u16) {
+        let mut index = self.lock_index();
+        if let Some(ids) = index.get_mut(&appid) {
+            ids.retain(|&id| id != pcs_id);
+            if ids.is_empty() {
+                index.remove(&appid);
+            }
+        }
+    }
+
+    /// Rebuild the whole index from the current contents of `pcs_data_pool`.
+    /// Call this after APPIDs are reassigned at runtime.
+    pub fn rebuild(&self, pcs_data_pool: &Mutex<HashMap<u16, SubscriberPCSData>>) {
+        let pool = match pcs_data_pool.lock() {
+            Ok(pool) => pool,
+            Err(poisoned) => {
+                warn!("AppIdIndex::rebuild: pcs_data_pool mutex was poisoned, recovering");
+                poisoned.into_inner()
+            }
+        };
+        let mut new_index: HashMap<u16, Vec<u16>> = HashMap::new();
+        for pcs in pool.values() {
+            if let Some(appid) = pcs.nameplate_appid() {
+                new_index.entry(appid).or_insert_with(Vec::new).push(pcs.pcs_id());
+            }
+        }
+        *self.lock_index() = new_index;
+        info!("AppIdIndex rebuilt: {} APPID(s) indexed", self.lock_index().len());
+    }
+
+    /// PCS ids registered for `appid`, empty if none match.
+    pub fn pcs_ids_for_appid(&self, appid: u16) -> Vec<u16> {
+        self.lock_index().get(&appid).cloned().unwrap_or_default()
+    }
+
+    fn lock_index(&self) -> std::sync::MutexGuard<'_, HashMap<u16, Vec<u16>>> {
+        match self.index.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                warn!("AppIdIndex: index mutex was poisoned, recovering");
+                poisoned.into_inner()
+            }
+        }
+    }
 }
\ No newline at end of file
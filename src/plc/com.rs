@@ -3,6 +3,8 @@ use crate::pcs::process_data::{AppIdIndex, MutablePcsData};
 use crate::plc::types::{StPCSDataBytePosInAllDataCfg, StPCSImage, StPCSinfo};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 // Global atomic counter for lifecounter - thread-safe auto-increment
 static LIFECOUNTER: AtomicU64 = AtomicU64::new(0);
@@ -116,28 +118,113 @@ pub fn get_stpcsimage(
 
 //todo: using socket2 to call udp socket functions send pcs image to plc
 
-use log::info;
-use socket2::Socket;
+use crate::plc::transport::PcsUdpSink;
+use log::{info, warn};
 // use std::net::SocketAddr;
 use std::io;
 
+/// Wire byte order for the multi-byte fields in `serialize_stpcsimage` /
+/// `serialize_stpcsinfo`. The original wire format is little-endian; PLCs and
+/// fieldbus endpoints that expect network byte order can be served by
+/// `BigEndian` instead, without changing the 49-byte record layout. The CRC-32
+/// trailer itself is always little-endian regardless of this setting, since
+/// it is not part of the PLC's data model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    #[default]
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    fn encode_u16(self, value: u16) -> [u8; 2] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+
+    fn encode_u64(self, value: u64) -> [u8; 8] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+
+    fn encode_f32(self, value: f32) -> [u8; 4] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+
+    fn decode_u16(self, bytes: [u8; 2]) -> u16 {
+        match self {
+            ByteOrder::LittleEndian => u16::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u16::from_be_bytes(bytes),
+        }
+    }
+
+    fn decode_u64(self, bytes: [u8; 8]) -> u64 {
+        match self {
+            ByteOrder::LittleEndian => u64::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u64::from_be_bytes(bytes),
+        }
+    }
+
+    fn decode_f32(self, bytes: [u8; 4]) -> f32 {
+        match self {
+            ByteOrder::LittleEndian => f32::from_le_bytes(bytes),
+            ByteOrder::BigEndian => f32::from_be_bytes(bytes),
+        }
+    }
+}
+
 /// Serialize stPCSImage to bytes for UDP transmission
 ///
 /// Binary format:
 /// - protocol (1 byte)
-/// - number_of_pcs (2 bytes)
-/// - lifecounter (8 bytes)
+/// - number_of_pcs (2 bytes, `byte_order`)
+/// - lifecounter (8 bytes, `byte_order`)
 /// - spare (16 bytes)
 /// - pcs_data_networkA + data
 /// - pcs_data_networkB + data
-fn serialize_stpcsimage(image: &StPCSImage) -> Vec<u8> {
-    let mut buffer = Vec::new();
+/// - crc32 (4 bytes, always little-endian, over every byte above)
+fn serialize_stpcsimage(image: &StPCSImage, byte_order: ByteOrder) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(stpcsimage_buffer_capacity(
+        image.pcs_data_networka.len() + image.pcs_data_networkb.len(),
+    ));
+    serialize_stpcsimage_into(&mut buffer, image, byte_order);
+    buffer
+}
+
+/// Header(27) + `max_pcs` × record(49) + CRC trailer(4) bytes, i.e. the
+/// buffer size [`serialize_stpcsimage_into`] needs to serialize an image
+/// with up to `max_pcs` total `StPCSinfo` records (both LANs combined)
+/// without reallocating. Callers of [`send_stpcsimage_udp_with_buf`] that
+/// know their worst-case PCS count up front can use this to reserve their
+/// reusable buffer's capacity before the first send, instead of letting it
+/// grow to steady state via the first call's push/extend calls.
+pub fn stpcsimage_buffer_capacity(max_pcs: usize) -> usize {
+    const HEADER_LEN: usize = 27;
+    const RECORD_LEN: usize = 49;
+    const CRC_LEN: usize = 4;
+    HEADER_LEN + RECORD_LEN * max_pcs + CRC_LEN
+}
+
+/// Like [`serialize_stpcsimage`], but writes into a caller-owned `buf`
+/// instead of allocating a fresh `Vec` every call. `buf` is cleared (without
+/// shrinking its capacity) before writing, so a long-lived sender thread can
+/// size it once with [`stpcsimage_buffer_capacity`] and reuse it call after
+/// call with no per-frame heap traffic.
+pub fn serialize_stpcsimage_into(buf: &mut Vec<u8>, image: &StPCSImage, byte_order: ByteOrder) {
+    buf.clear();
 
     // Header: protocol(1) + number_of_pcs(2) + lifecounter(8) + spare(16) = 27 bytes
-    buffer.push(image.protocol);
-    buffer.extend_from_slice(&image.number_of_pcs.to_le_bytes());
-    buffer.extend_from_slice(&image.lifecounter.to_le_bytes());
-    buffer.extend_from_slice(&image.spare);
+    buf.push(image.protocol);
+    buf.extend_from_slice(&byte_order.encode_u16(image.number_of_pcs));
+    buf.extend_from_slice(&byte_order.encode_u64(image.lifecounter));
+    buf.extend_from_slice(&image.spare);
 
     // Network A data - sort indices only (much cheaper than cloning structs)
     // PERFORMANCE: Sorting indices (8 bytes × N) vs cloning structs (49 bytes × N)
@@ -145,68 +232,682 @@ fn serialize_stpcsimage(image: &StPCSImage) -> Vec<u8> {
     let mut indices_a: Vec<usize> = (0..image.pcs_data_networka.len()).collect();
     indices_a.sort_unstable_by_key(|&i| image.pcs_data_networka[i].logical_id);
     for &idx in &indices_a {
-        serialize_stpcsinfo(&mut buffer, &image.pcs_data_networka[idx]);
+        serialize_stpcsinfo(buf, &image.pcs_data_networka[idx], byte_order);
     }
 
     // Network B data - same optimization
     let mut indices_b: Vec<usize> = (0..image.pcs_data_networkb.len()).collect();
     indices_b.sort_unstable_by_key(|&i| image.pcs_data_networkb[i].logical_id);
     for &idx in &indices_b {
-        serialize_stpcsinfo(&mut buffer, &image.pcs_data_networkb[idx]);
+        serialize_stpcsinfo(buf, &image.pcs_data_networkb[idx], byte_order);
     }
 
-    buffer
+    let crc = crc32(buf);
+    buf.extend_from_slice(&crc.to_le_bytes());
+}
+
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+/// The standard reflected CRC-32 lookup table (poly 0xEDB88320), built once on
+/// first use instead of on every call to [`crc32`].
+fn crc32_table() -> &'static [u32; 256] {
+    static TABLE: OnceLock<[u32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ CRC32_POLYNOMIAL
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    })
+}
+
+/// Reflected CRC-32 (polynomial 0xEDB88320, init/final XOR 0xFFFFFFFF) used to
+/// detect a corrupted `serialize_stpcsimage` datagram before the PLC trusts it.
+fn crc32(data: &[u8]) -> u32 {
+    let table = crc32_table();
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        let index = ((crc ^ byte as u32) & 0xFF) as usize;
+        crc = (crc >> 8) ^ table[index];
+    }
+    crc ^ 0xFFFF_FFFF
 }
 
 /// Serialize stPCSinfo to bytes
 ///
 /// Binary format (49 bytes per PCS):
-/// - logical_id (2 bytes)
+/// - logical_id (2 bytes, `byte_order`)
 /// - is_valid (1 byte)
 /// - feed_line_id (1 byte)
 /// - is_controllable (1 byte)
-/// - pcs_realtime_active_power (4 bytes f32)
-/// - pcs_realtime_reactive_power (4 bytes f32)
-/// - pcs_maximum_charging_power (4 bytes f32)
-/// - pcs_maximum_discharging_power (4 bytes f32)
-/// - pcs_maximum_inductive_power (4 bytes f32)
-/// - pcs_maximum_capacitive_power (4 bytes f32)
-/// - SOC (4 bytes f32)
+/// - pcs_realtime_active_power (4 bytes f32, `byte_order`)
+/// - pcs_realtime_reactive_power (4 bytes f32, `byte_order`)
+/// - pcs_maximum_charging_power (4 bytes f32, `byte_order`)
+/// - pcs_maximum_discharging_power (4 bytes f32, `byte_order`)
+/// - pcs_maximum_inductive_power (4 bytes f32, `byte_order`)
+/// - pcs_maximum_capacitive_power (4 bytes f32, `byte_order`)
+/// - SOC (4 bytes f32, `byte_order`)
 /// - spare (16 bytes)
-fn serialize_stpcsinfo(buffer: &mut Vec<u8>, pcs: &StPCSinfo) {
-    buffer.extend_from_slice(&pcs.logical_id.to_le_bytes());
+fn serialize_stpcsinfo(buffer: &mut Vec<u8>, pcs: &StPCSinfo, byte_order: ByteOrder) {
+    buffer.extend_from_slice(&byte_order.encode_u16(pcs.logical_id));
     buffer.push(pcs.is_valid);
     buffer.push(pcs.feed_line_id);
     buffer.push(pcs.is_controllable);
-    buffer.extend_from_slice(&pcs.pcs_realtime_active_power.to_le_bytes());
-    buffer.extend_from_slice(&pcs.pcs_realtime_reactive_power.to_le_bytes());
-    buffer.extend_from_slice(&pcs.pcs_maximum_charging_power.to_le_bytes());
-    buffer.extend_from_slice(&pcs.pcs_maximum_discharging_power.to_le_bytes());
-    buffer.extend_from_slice(&pcs.pcs_maximum_inductive_power.to_le_bytes());
-    buffer.extend_from_slice(&pcs.pcs_maximum_capacitive_power.to_le_bytes());
-    buffer.extend_from_slice(&pcs.pcs_soc.to_le_bytes());
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_realtime_active_power));
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_realtime_reactive_power));
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_maximum_charging_power));
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_maximum_discharging_power));
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_maximum_inductive_power));
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_maximum_capacitive_power));
+    buffer.extend_from_slice(&byte_order.encode_f32(pcs.pcs_soc));
     buffer.extend_from_slice(&pcs.spare);
 }
 
-/// Send stPCSImage via UDP using pre-existing socket
+/// Deserialize an `StPCSinfo` record from its 49-byte wire form (the
+/// reciprocal of `serialize_stpcsinfo`).
+fn deserialize_stpcsinfo(bytes: &[u8], byte_order: ByteOrder) -> Result<StPCSinfo, String> {
+    if bytes.len() != 49 {
+        return Err(format!(
+            "stPCSinfo record must be 49 bytes, got {}",
+            bytes.len()
+        ));
+    }
+
+    Ok(StPCSinfo {
+        logical_id: byte_order.decode_u16(bytes[0..2].try_into().unwrap()),
+        is_valid: bytes[2],
+        feed_line_id: bytes[3],
+        is_controllable: bytes[4],
+        pcs_realtime_active_power: byte_order.decode_f32(bytes[5..9].try_into().unwrap()),
+        pcs_realtime_reactive_power: byte_order.decode_f32(bytes[9..13].try_into().unwrap()),
+        pcs_maximum_charging_power: byte_order.decode_f32(bytes[13..17].try_into().unwrap()),
+        pcs_maximum_discharging_power: byte_order.decode_f32(bytes[17..21].try_into().unwrap()),
+        pcs_maximum_inductive_power: byte_order.decode_f32(bytes[21..25].try_into().unwrap()),
+        pcs_maximum_capacitive_power: byte_order.decode_f32(bytes[25..29].try_into().unwrap()),
+        pcs_soc: byte_order.decode_f32(bytes[29..33].try_into().unwrap()),
+        spare: bytes[33..49].try_into().unwrap(),
+    })
+}
+
+/// Deserialize a `serialize_stpcsimage` buffer, validating the trailing
+/// CRC-32 before trusting any of it.
+///
+/// Note: `number_of_pcs` is a single total and the wire format has no length
+/// prefix separating network A from network B, so every 49-byte record found
+/// after the 27-byte header is returned in `pcs_data_networka`; callers that
+/// split PCS data across both LANs need a side channel (e.g. a config-driven
+/// logical_id range) to re-partition them, same as `serialize_stpcsimage`
+/// gives no such split on the wire today.
+///
+/// # Errors
+/// Returns `Err` if the buffer is shorter than a header + CRC trailer, the
+/// record data isn't a whole number of 49-byte records, or the trailing
+/// CRC-32 doesn't match the checksum recomputed over everything before it.
+///
+/// `byte_order` must match whatever `serialize_stpcsimage` used to produce
+/// `data` - the CRC-32 trailer itself is always little-endian.
+pub fn deserialize_stpcsimage(data: &[u8], byte_order: ByteOrder) -> Result<StPCSImage, String> {
+    const HEADER_LEN: usize = 27;
+    const RECORD_LEN: usize = 49;
+    const CRC_LEN: usize = 4;
+
+    if data.len() < HEADER_LEN + CRC_LEN {
+        return Err(format!(
+            "stPCSImage buffer too short: need at least {} bytes, got {}",
+            HEADER_LEN + CRC_LEN,
+            data.len()
+        ));
+    }
+
+    let crc_offset = data.len() - CRC_LEN;
+    let expected_crc = u32::from_le_bytes(data[crc_offset..].try_into().unwrap());
+    let actual_crc = crc32(&data[..crc_offset]);
+    if actual_crc != expected_crc {
+        return Err(format!(
+            "stPCSImage CRC mismatch: expected {:#010x}, computed {:#010x}",
+            expected_crc, actual_crc
+        ));
+    }
+
+    let mut image = StPCSImage {
+        protocol: data[0],
+        number_of_pcs: byte_order.decode_u16(data[1..3].try_into().unwrap()),
+        lifecounter: byte_order.decode_u64(data[3..11].try_into().unwrap()),
+        spare: data[11..27].try_into().unwrap(),
+        ..StPCSImage::default()
+    };
+
+    let records = &data[HEADER_LEN..crc_offset];
+    if records.len() % RECORD_LEN != 0 {
+        return Err(format!(
+            "stPCSImage record data length {} is not a multiple of {} bytes",
+            records.len(),
+            RECORD_LEN
+        ));
+    }
+
+    for chunk in records.chunks_exact(RECORD_LEN) {
+        image
+            .pcs_data_networka
+            .push(deserialize_stpcsinfo(chunk, byte_order)?);
+    }
+
+    Ok(image)
+}
+
+/// Number of bytes the 6LoWPAN-style fragment header in [`fragment_image`]
+/// occupies at the front of every datagram it produces.
+const FRAGMENT_HEADER_LEN: usize = 16;
+
+/// Safe default maximum UDP payload (bytes) per fragment, comfortably under
+/// the common 1500-byte Ethernet MTU once IP/UDP headers are subtracted.
+pub const DEFAULT_MAX_FRAGMENT_PAYLOAD: usize = 1400;
+
+/// Application-layer fragment header prefixed to every datagram
+/// `send_stpcsimage_udp` sends, analogous to a 6LoWPAN fragment header:
+/// `datagram_id` (the image's lifecounter) groups a datagram's fragments,
+/// `fragment_index`/`fragment_count` let [`FragmentReassembler`] detect
+/// completeness despite out-of-order arrival, and `total_len` validates the
+/// reassembled size before its CRC-32 trailer is even checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FragmentHeader {
+    datagram_id: u64,
+    fragment_index: u16,
+    fragment_count: u16,
+    total_len: u32,
+}
+
+impl FragmentHeader {
+    fn encode(self) -> [u8; FRAGMENT_HEADER_LEN] {
+        let mut out = [0u8; FRAGMENT_HEADER_LEN];
+        out[0..8].copy_from_slice(&self.datagram_id.to_le_bytes());
+        out[8..10].copy_from_slice(&self.fragment_index.to_le_bytes());
+        out[10..12].copy_from_slice(&self.fragment_count.to_le_bytes());
+        out[12..16].copy_from_slice(&self.total_len.to_le_bytes());
+        out
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < FRAGMENT_HEADER_LEN {
+            return Err(format!(
+                "Fragment datagram too short: need at least {} bytes, got {}",
+                FRAGMENT_HEADER_LEN,
+                bytes.len()
+            ));
+        }
+        Ok(Self {
+            datagram_id: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            fragment_index: u16::from_le_bytes(bytes[8..10].try_into().unwrap()),
+            fragment_count: u16::from_le_bytes(bytes[10..12].try_into().unwrap()),
+            total_len: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+        })
+    }
+}
+
+/// Split a serialized `stPCSImage` buffer into fragment-header-prefixed
+/// datagrams no larger than `max_payload` payload bytes each, all sharing
+/// `datagram_id` (the image's lifecounter).
+fn fragment_image(data: &[u8], datagram_id: u64, max_payload: usize) -> Vec<Vec<u8>> {
+    let max_payload = max_payload.max(1);
+    let fragment_count = data.len().div_ceil(max_payload).max(1) as u16;
+    let total_len = data.len() as u32;
+
+    let mut fragments: Vec<Vec<u8>> = data
+        .chunks(max_payload)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let header = FragmentHeader {
+                datagram_id,
+                fragment_index: index as u16,
+                fragment_count,
+                total_len,
+            };
+            let mut datagram = Vec::with_capacity(FRAGMENT_HEADER_LEN + chunk.len());
+            datagram.extend_from_slice(&header.encode());
+            datagram.extend_from_slice(chunk);
+            datagram
+        })
+        .collect();
+
+    // data.chunks() yields nothing for an empty buffer; an image is never
+    // actually empty, but keep a single empty fragment so downstream
+    // reassembly always sees a complete (count >= 1) datagram.
+    if fragments.is_empty() {
+        let header = FragmentHeader {
+            datagram_id,
+            fragment_index: 0,
+            fragment_count: 1,
+            total_len,
+        };
+        fragments.push(header.encode().to_vec());
+    }
+
+    fragments
+}
+
+/// One datagram's worth of fragments collected so far, keyed by
+/// `(lifecounter, fragment_index)` via the nested `fragments` map.
+struct PartialDatagram {
+    fragment_count: u16,
+    total_len: u32,
+    fragments: HashMap<u16, Vec<u8>>,
+    first_seen: Instant,
+}
+
+/// Reassembles datagrams fragmented by [`fragment_image`], tolerating
+/// out-of-order fragment arrival the same way a 6LoWPAN reassembler does:
+/// fragments are buffered per `datagram_id` until every `fragment_index` in
+/// `0..fragment_count` has been seen, or until `max_age` has passed without
+/// completing (at which point the partial datagram is dropped and logged as
+/// a gap/timeout rather than held forever).
+pub struct FragmentReassembler {
+    max_age: Duration,
+    partial: HashMap<u64, PartialDatagram>,
+}
+
+impl FragmentReassembler {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            partial: HashMap::new(),
+        }
+    }
+
+    /// Feed one received UDP datagram produced by `send_stpcsimage_udp`.
+    /// Returns the fully reassembled serialized image once every fragment of
+    /// its `datagram_id` has arrived, or `Ok(None)` while it is still
+    /// incomplete.
+    ///
+    /// # Errors
+    /// Returns `Err` if the fragment header is malformed or a later fragment
+    /// disagrees with an earlier one's `fragment_count`/`total_len` for the
+    /// same `datagram_id`.
+    pub fn accept(&mut self, datagram: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        let header = FragmentHeader::decode(datagram)?;
+        let payload = datagram[FRAGMENT_HEADER_LEN..].to_vec();
+
+        self.drop_stale_datagrams();
+
+        let is_complete = {
+            let entry = self.partial.entry(header.datagram_id).or_insert_with(|| PartialDatagram {
+                fragment_count: header.fragment_count,
+                total_len: header.total_len,
+                fragments: HashMap::new(),
+                first_seen: Instant::now(),
+            });
+
+            if entry.fragment_count != header.fragment_count || entry.total_len != header.total_len {
+                return Err(format!(
+                    "Fragment header mismatch for datagram {}: expected {} fragment(s)/{} bytes, got {}/{}",
+                    header.datagram_id, entry.fragment_count, entry.total_len,
+                    header.fragment_count, header.total_len
+                ));
+            }
+
+            entry.fragments.insert(header.fragment_index, payload);
+            entry.fragments.len() >= entry.fragment_count as usize
+        };
+
+        if !is_complete {
+            return Ok(None);
+        }
+
+        let partial = self
+            .partial
+            .remove(&header.datagram_id)
+            .expect("datagram_id was just inserted/looked up above");
+
+        // Don't pre-allocate against the peer-declared `total_len`: it hasn't
+        // been cross-checked against the fragments actually received yet, so
+        // a corrupted/malicious header could otherwise trigger an
+        // oversized allocation before the length check below ever runs.
+        let mut reassembled = Vec::new();
+        for index in 0..partial.fragment_count {
+            match partial.fragments.get(&index) {
+                Some(chunk) => reassembled.extend_from_slice(chunk),
+                None => {
+                    return Err(format!(
+                        "Datagram {} missing fragment {} despite a complete fragment count",
+                        header.datagram_id, index
+                    ));
+                }
+            }
+        }
+
+        if reassembled.len() as u32 != partial.total_len {
+            return Err(format!(
+                "Datagram {} reassembled to {} bytes, expected {}",
+                header.datagram_id,
+                reassembled.len(),
+                partial.total_len
+            ));
+        }
+
+        Ok(Some(reassembled))
+    }
+
+    /// Drop (and log) any datagram that has been incomplete for longer than `max_age`.
+    fn drop_stale_datagrams(&mut self) {
+        let max_age = self.max_age;
+        self.partial.retain(|&datagram_id, partial| {
+            let stale = partial.first_seen.elapsed() >= max_age;
+            if stale {
+                warn!(
+                    "FragmentReassembler: dropping datagram {} after {:?}, only {}/{} fragment(s) received",
+                    datagram_id,
+                    partial.first_seen.elapsed(),
+                    partial.fragments.len(),
+                    partial.fragment_count
+                );
+            }
+            !stale
+        });
+    }
+}
+
+/// Send stPCSImage via UDP using pre-existing socket, fragmenting the
+/// serialized image across multiple datagrams if it exceeds
+/// `max_fragment_payload` bytes.
 ///
 /// Uses socket created during program initialization.
 ///
+/// `sink` is generic over [`PcsUdpSink`] rather than tied to
+/// `socket2::Socket` directly, so the same serialization/fragmentation path
+/// can flow over a `no_std` embedded transport (see
+/// [`transport::SmoltcpSink`](crate::plc::transport::SmoltcpSink)); every
+/// hosted caller keeps passing a plain `&socket2::Socket` unchanged, since it
+/// implements `PcsUdpSink` directly.
+///
 /// # Arguments
-/// * `socket` - Reusable socket2::Socket (must be bound)
+/// * `sink` - Reusable egress (e.g. a bound `socket2::Socket`)
 /// * `image` - Reference to stPCSImage to send
+/// * `byte_order` - Wire byte order for `number_of_pcs`/`lifecounter`/f32 fields
+/// * `max_fragment_payload` - Maximum payload bytes per UDP datagram (see
+///   [`DEFAULT_MAX_FRAGMENT_PAYLOAD`])
 ///
 /// # Returns
-/// * `Ok(usize)` - Number of bytes sent
-/// * `Err(io::Error)` - Error if send fails
-pub fn send_stpcsimage_udp(socket: &Socket, image: &StPCSImage) -> io::Result<usize> {
-    let data = serialize_stpcsimage(image);
+/// * `Ok(usize)` - Total bytes sent across all fragments
+/// * `Err(io::Error)` - Error if any fragment's send fails
+pub fn send_stpcsimage_udp<S: PcsUdpSink>(
+    sink: &S,
+    image: &StPCSImage,
+    byte_order: ByteOrder,
+    max_fragment_payload: usize,
+) -> io::Result<usize> {
+    let data = serialize_stpcsimage(image, byte_order);
+    let fragments = fragment_image(&data, image.lifecounter, max_fragment_payload);
 
     log::debug!(
-        "Sending pcs image to tc via reusable UDP socket: lifecounter={}, size={} bytes",
+        "Sending pcs image to tc via reusable UDP socket: lifecounter={}, size={} bytes across {} fragment(s)",
         image.lifecounter,
-        data.len()
+        data.len(),
+        fragments.len()
     );
 
-    socket.send(&data)
+    let mut total_sent = 0;
+    for fragment in &fragments {
+        total_sent += sink.send(fragment)?;
+    }
+    Ok(total_sent)
+}
+
+/// Like [`send_stpcsimage_udp`], but serializes into a caller-owned `buf`
+/// (via [`serialize_stpcsimage_into`]) instead of allocating a fresh `Vec`
+/// per call. `buf` is reused across calls - optionally pre-sized with
+/// [`stpcsimage_buffer_capacity`] to avoid even the first call's growth -
+/// so a long-lived sender thread can transmit at high rate with no
+/// steady-state per-frame heap traffic; `fragment_image` still allocates
+/// one `Vec` per outgoing datagram. See [`send_stpcsimage_udp`] for why
+/// `sink` is generic over [`PcsUdpSink`].
+///
+/// # Arguments
+/// * `sink` - Reusable egress (e.g. a bound `socket2::Socket`)
+/// * `image` - Reference to stPCSImage to send
+/// * `byte_order` - Wire byte order for `number_of_pcs`/`lifecounter`/f32 fields
+/// * `max_fragment_payload` - Maximum payload bytes per UDP datagram (see
+///   [`DEFAULT_MAX_FRAGMENT_PAYLOAD`])
+/// * `buf` - Reusable scratch buffer the serialized image is written into
+///
+/// # Returns
+/// * `Ok(usize)` - Total bytes sent across all fragments
+/// * `Err(io::Error)` - Error if any fragment's send fails
+pub fn send_stpcsimage_udp_with_buf<S: PcsUdpSink>(
+    sink: &S,
+    image: &StPCSImage,
+    byte_order: ByteOrder,
+    max_fragment_payload: usize,
+    buf: &mut Vec<u8>,
+) -> io::Result<usize> {
+    serialize_stpcsimage_into(buf, image, byte_order);
+    let fragments = fragment_image(buf, image.lifecounter, max_fragment_payload);
+
+    log::debug!(
+        "Sending pcs image to tc via reusable UDP socket (reusable buf): lifecounter={}, size={} bytes across {} fragment(s)",
+        image.lifecounter,
+        buf.len(),
+        fragments.len()
+    );
+
+    let mut total_sent = 0;
+    for fragment in &fragments {
+        total_sent += sink.send(fragment)?;
+    }
+    Ok(total_sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_info(logical_id: u16) -> StPCSinfo {
+        StPCSinfo {
+            logical_id,
+            is_valid: 1,
+            feed_line_id: 3,
+            is_controllable: 1,
+            pcs_realtime_active_power: 12.5,
+            pcs_realtime_reactive_power: -4.25,
+            pcs_maximum_charging_power: 100.0,
+            pcs_maximum_discharging_power: 90.0,
+            pcs_maximum_inductive_power: 50.0,
+            pcs_maximum_capacitive_power: 50.0,
+            pcs_soc: 0.75,
+            spare: [0u8; 16],
+        }
+    }
+
+    #[test]
+    fn test_crc32_matches_known_vector() {
+        // "123456789" is the standard CRC-32/ISO-HDLC check value.
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_serialize_deserialize_round_trip_little_endian() {
+        let mut image = StPCSImage {
+            protocol: 1,
+            number_of_pcs: 2,
+            lifecounter: 42,
+            spare: [7u8; 16],
+            ..StPCSImage::default()
+        };
+        image.pcs_data_networka.push(sample_info(5));
+        image.pcs_data_networka.push(sample_info(1));
+
+        let bytes = serialize_stpcsimage(&image, ByteOrder::LittleEndian);
+        let decoded = deserialize_stpcsimage(&bytes, ByteOrder::LittleEndian)
+            .expect("round trip should succeed");
+
+        assert_eq!(decoded.protocol, image.protocol);
+        assert_eq!(decoded.number_of_pcs, image.number_of_pcs);
+        assert_eq!(decoded.lifecounter, image.lifecounter);
+        assert_eq!(decoded.spare, image.spare);
+        // serialize_stpcsimage sorts by logical_id, so the decoded order is 1, 5.
+        assert_eq!(decoded.pcs_data_networka.len(), 2);
+        assert_eq!(decoded.pcs_data_networka[0].logical_id, 1);
+        assert_eq!(decoded.pcs_data_networka[1].logical_id, 5);
+        assert_eq!(
+            decoded.pcs_data_networka[1].pcs_realtime_active_power,
+            12.5
+        );
+    }
+
+    #[test]
+    fn test_serialize_deserialize_round_trip_big_endian() {
+        let mut image = StPCSImage {
+            protocol: 1,
+            number_of_pcs: 2,
+            lifecounter: 42,
+            spare: [7u8; 16],
+            ..StPCSImage::default()
+        };
+        image.pcs_data_networka.push(sample_info(5));
+
+        let le_bytes = serialize_stpcsimage(&image, ByteOrder::LittleEndian);
+        let be_bytes = serialize_stpcsimage(&image, ByteOrder::BigEndian);
+        assert_ne!(le_bytes, be_bytes, "endianness should change the wire bytes");
+
+        let decoded = deserialize_stpcsimage(&be_bytes, ByteOrder::BigEndian)
+            .expect("round trip should succeed");
+        assert_eq!(decoded.number_of_pcs, image.number_of_pcs);
+        assert_eq!(decoded.lifecounter, image.lifecounter);
+        assert_eq!(decoded.pcs_data_networka[0].logical_id, 5);
+        assert_eq!(
+            decoded.pcs_data_networka[0].pcs_realtime_active_power,
+            12.5
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_corrupted_buffer() {
+        let image = StPCSImage {
+            protocol: 1,
+            number_of_pcs: 0,
+            lifecounter: 1,
+            ..StPCSImage::default()
+        };
+        let mut bytes = serialize_stpcsimage(&image, ByteOrder::LittleEndian);
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+
+        assert!(deserialize_stpcsimage(&bytes, ByteOrder::LittleEndian).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_truncated_buffer() {
+        assert!(deserialize_stpcsimage(&[0u8; 10], ByteOrder::LittleEndian).is_err());
+    }
+
+    #[test]
+    fn test_fragment_image_splits_on_max_payload() {
+        let data = vec![0xAB_u8; 250];
+        let fragments = fragment_image(&data, 7, 100);
+
+        assert_eq!(fragments.len(), 3);
+        for fragment in &fragments {
+            assert!(fragment.len() <= FRAGMENT_HEADER_LEN + 100);
+        }
+        assert_eq!(fragments[0].len(), FRAGMENT_HEADER_LEN + 100);
+        assert_eq!(fragments[2].len(), FRAGMENT_HEADER_LEN + 50);
+    }
+
+    #[test]
+    fn test_reassembler_reassembles_out_of_order_fragments() {
+        let data: Vec<u8> = (0..250u16).map(|b| b as u8).collect();
+        let mut fragments = fragment_image(&data, 7, 100);
+        fragments.reverse();
+
+        let mut reassembler = FragmentReassembler::new(Duration::from_secs(1));
+        let mut reassembled = None;
+        for fragment in &fragments {
+            reassembled = reassembler.accept(fragment).expect("valid fragment");
+        }
+
+        assert_eq!(reassembled, Some(data));
+        assert_eq!(reassembler.partial.len(), 0);
+    }
+
+    #[test]
+    fn test_reassembler_rejects_header_mismatch() {
+        let data = vec![1u8; 150];
+        let fragments = fragment_image(&data, 7, 100);
+        let mut mismatched = fragments[0].clone();
+        mismatched[12..16].copy_from_slice(&999u32.to_le_bytes());
+
+        let mut reassembler = FragmentReassembler::new(Duration::from_secs(1));
+        reassembler.accept(&fragments[0]).unwrap();
+        assert!(reassembler.accept(&mismatched).is_err());
+    }
+
+    #[test]
+    fn test_reassembler_drops_stale_datagrams() {
+        let data = vec![1u8; 250];
+        let fragments = fragment_image(&data, 7, 100);
+
+        let mut reassembler = FragmentReassembler::new(Duration::from_millis(0));
+        let result = reassembler.accept(&fragments[0]).expect("valid fragment");
+        assert_eq!(result, None);
+
+        // The next accept() call sweeps stale entries before inserting, so
+        // the first (already-stale) fragment is dropped and this partial
+        // datagram starts fresh rather than completing.
+        let result = reassembler.accept(&fragments[1]).expect("valid fragment");
+        assert_eq!(result, None);
+        assert_eq!(reassembler.partial.len(), 1);
+    }
+
+    #[test]
+    fn test_serialize_into_matches_allocating_serialize() {
+        let mut image = StPCSImage {
+            protocol: 1,
+            number_of_pcs: 2,
+            lifecounter: 42,
+            spare: [7u8; 16],
+            ..StPCSImage::default()
+        };
+        image.pcs_data_networka.push(sample_info(5));
+        image.pcs_data_networka.push(sample_info(1));
+
+        let expected = serialize_stpcsimage(&image, ByteOrder::LittleEndian);
+
+        let mut buf = Vec::new();
+        serialize_stpcsimage_into(&mut buf, &image, ByteOrder::LittleEndian);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_serialize_into_reuses_buffer_capacity() {
+        let image = StPCSImage {
+            protocol: 1,
+            number_of_pcs: 0,
+            lifecounter: 1,
+            ..StPCSImage::default()
+        };
+
+        let mut buf = vec![0xAAu8; 512];
+        buf.truncate(0);
+        assert_eq!(buf.capacity(), 512);
+
+        serialize_stpcsimage_into(&mut buf, &image, ByteOrder::LittleEndian);
+        assert_eq!(buf.capacity(), 512, "clear() must not shrink capacity");
+        assert_eq!(buf.len(), 27 + 4);
+    }
+
+    #[test]
+    fn test_stpcsimage_buffer_capacity() {
+        assert_eq!(stpcsimage_buffer_capacity(0), 31);
+        assert_eq!(stpcsimage_buffer_capacity(10), 27 + 49 * 10 + 4);
+    }
 }
@@ -0,0 +1,69 @@
+//! Egress abstraction for sending a serialized `stPCSImage` datagram.
+//!
+//! `send_stpcsimage_udp` used to be hard-wired to `socket2::Socket`, which
+//! needs a hosted OS and rules out ever running this simulator on bare-metal
+//! or RTOS targets. `PcsUdpSink` is the narrow trait it sends fragments
+//! through instead: `socket2::Socket` implements it directly so every
+//! existing hosted call site (`plc::com`, `threads::plc_retransmit`) keeps
+//! working unchanged, and [`SmoltcpSink`] backs the same trait with a
+//! smoltcp UDP socket handle for `no_std` embedded builds, gated behind the
+//! `smoltcp-transport` cargo feature. Mirrors the backend-trait split
+//! `crate::os::rt_platform::RtPlatform` uses for real-time thread setup:
+//! only the egress step is abstracted here, the serialization path
+//! (`serialize_stpcsimage`) is identical on every backend.
+
+use std::io;
+
+/// Send one outgoing datagram, returning the number of bytes sent on
+/// success. Mirrors `socket2::Socket::send`'s signature so the hosted
+/// backend is a zero-cost pass-through.
+pub trait PcsUdpSink {
+    fn send(&self, data: &[u8]) -> io::Result<usize>;
+}
+
+impl PcsUdpSink for socket2::Socket {
+    fn send(&self, data: &[u8]) -> io::Result<usize> {
+        socket2::Socket::send(self, data)
+    }
+}
+
+/// `no_std` embedded backend over a smoltcp UDP socket handle, enabled with
+/// the `smoltcp-transport` cargo feature.
+///
+/// smoltcp's `udp::Socket::send_slice` needs `&mut self`, so the handle is
+/// held behind a `RefCell` to satisfy `PcsUdpSink::send`'s `&self` - the same
+/// shared-reference shape `send_stpcsimage_udp` already passes a
+/// `socket2::Socket` through. `send` queues `data` onto the socket's TX
+/// buffer; it is the caller's responsibility to keep polling the smoltcp
+/// interface so the queued datagram actually goes out.
+#[cfg(feature = "smoltcp-transport")]
+pub struct SmoltcpSink<'a> {
+    socket: core::cell::RefCell<&'a mut smoltcp::socket::udp::Socket<'a>>,
+    remote: smoltcp::wire::IpEndpoint,
+}
+
+#[cfg(feature = "smoltcp-transport")]
+impl<'a> SmoltcpSink<'a> {
+    /// `socket` must already be bound to a local endpoint; `remote` is the
+    /// PLC's address every `send` targets.
+    pub fn new(
+        socket: &'a mut smoltcp::socket::udp::Socket<'a>,
+        remote: smoltcp::wire::IpEndpoint,
+    ) -> Self {
+        Self {
+            socket: core::cell::RefCell::new(socket),
+            remote,
+        }
+    }
+}
+
+#[cfg(feature = "smoltcp-transport")]
+impl PcsUdpSink for SmoltcpSink<'_> {
+    fn send(&self, data: &[u8]) -> io::Result<usize> {
+        let mut socket = self.socket.borrow_mut();
+        socket
+            .send_slice(data, self.remote)
+            .map(|()| data.len())
+            .map_err(|e| io::Error::other(format!("smoltcp UDP send failed: {:?}", e)))
+    }
+}
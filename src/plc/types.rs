@@ -1,12 +1,13 @@
 // rs to plc data structure
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use log::{error, info};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter};
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 
 use crate::pcs::SubscriberPCSData;
 
@@ -71,37 +72,113 @@ impl Default for StPCSinfo {
 }
 
 /// Configuration for byte positions of PCS data in GOOSE PDU allData field
+/// Wire representation and scaling of a single allData measurand.
+///
+/// Real IEC 61850 GOOSE datasets encode measurands as INT16/INT32, scaled
+/// integers, or floats, not just `float32`. `get_info` dispatches on `kind`
+/// and applies `raw * scale + offset` before storing the result.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    Float32,
+    Int16,
+    Int32,
+    ScaledInt,
+    Bool,
+}
+
+impl Default for FieldKind {
+    fn default() -> Self {
+        FieldKind::Float32
+    }
+}
+
+fn default_field_scale() -> f32 {
+    1.0
+}
+
+/// Descriptor for a single allData measurand position: byte `pos`, wire `kind`,
+/// and the `raw * scale + offset` transform applied before storing into `StPCSinfo`.
+///
+/// Deserializes from either a bare position number (legacy JSON files, defaults
+/// to `Float32`/`scale=1.0`/`offset=0.0`) or a full `{ pos, kind, scale, offset }` object.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FieldDescriptor {
+    pub pos: usize,
+    pub kind: FieldKind,
+    pub scale: f32,
+    pub offset: f32,
+}
+
+impl FieldDescriptor {
+    pub fn at(pos: usize) -> Self {
+        Self {
+            pos,
+            kind: FieldKind::Float32,
+            scale: 1.0,
+            offset: 0.0,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldDescriptor {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Pos(usize),
+            Full {
+                pos: usize,
+                #[serde(default)]
+                kind: FieldKind,
+                #[serde(default = "default_field_scale")]
+                scale: f32,
+                #[serde(default)]
+                offset: f32,
+            },
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Pos(pos) => FieldDescriptor::at(pos),
+            Raw::Full { pos, kind, scale, offset } => FieldDescriptor { pos, kind, scale, offset },
+        })
+    }
+}
+
 /// This maps the position of each data field for a specific PCS type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StPCSDataBytePosInAllDataCfg {
     /// PCS type identifier (e.g., "PCS-A", "PCS-B")
     pub pcstype: String,
-    
+
     /// Number of PCS devices of this type
     pub quantityofthistype: usize,
-    
-    /// Byte position of realtime active power in allData
-    pub pcs_realtime_active_power_pos: usize,
-    
-    /// Byte position of realtime reactive power in allData
-    pub pcs_realtime_reactive_power_pos: usize,
-    
-    /// Byte position of maximum charging power in allData
-    pub pcs_maximum_charging_power_pos: usize,
-    
-    /// Byte position of maximum discharging power in allData
-    pub pcs_maximum_discharging_power_pos: usize,
-    
-    /// Byte position of maximum inductive power in allData
-    pub pcs_maximum_inductive_power_pos: usize,
-    
-    /// Byte position of maximum capacitive power in allData
-    pub pcs_maximum_capacitive_power_pos: usize,
-    
-    /// Byte position of State of Charge (SOC) in allData
-    pub pcs_soc_pos: usize,
-    
-    /// Byte position of PCS status in allData
+
+    /// Byte position/kind/scale/offset of realtime active power in allData
+    pub pcs_realtime_active_power_pos: FieldDescriptor,
+
+    /// Byte position/kind/scale/offset of realtime reactive power in allData
+    pub pcs_realtime_reactive_power_pos: FieldDescriptor,
+
+    /// Byte position/kind/scale/offset of maximum charging power in allData
+    pub pcs_maximum_charging_power_pos: FieldDescriptor,
+
+    /// Byte position/kind/scale/offset of maximum discharging power in allData
+    pub pcs_maximum_discharging_power_pos: FieldDescriptor,
+
+    /// Byte position/kind/scale/offset of maximum inductive power in allData
+    pub pcs_maximum_inductive_power_pos: FieldDescriptor,
+
+    /// Byte position/kind/scale/offset of maximum capacitive power in allData
+    pub pcs_maximum_capacitive_power_pos: FieldDescriptor,
+
+    /// Byte position/kind/scale/offset of State of Charge (SOC) in allData
+    pub pcs_soc_pos: FieldDescriptor,
+
+    /// Byte position of PCS status in allData (status is read as float/u8, not descriptor-driven)
     pub pcs_status_pos: usize,
 
     /// pcs controllable status values
@@ -188,6 +265,93 @@ pub fn load_pcs_alldata_config<P: AsRef<Path>>(
     Ok(result)
 }
 
+/// Validate a single PCS allData config entry the same way `load_pcs_alldata_config` does
+fn validate_pcs_alldata_config(config: &StPCSDataBytePosInAllDataCfg) -> Result<()> {
+    if config.pcstype.trim().is_empty() {
+        anyhow::bail!("Invalid configuration: pcstype cannot be empty");
+    }
+    if config.quantityofthistype == 0 {
+        anyhow::bail!(
+            "Invalid configuration for {}: quantityofthistype must be > 0",
+            config.pcstype
+        );
+    }
+    Ok(())
+}
+
+/// Runtime-mutable, shareable store for the PCS allData byte-position configuration.
+///
+/// Wraps the `HashMap<String, (StPCSDataBytePosInAllDataCfg, Vec<u8>)>` produced by
+/// `load_pcs_alldata_config` behind an `Arc<RwLock<..>>` so operators can correct or add
+/// entries (e.g. a wrong `pcs_soc_pos`) while `get_info` keeps reading a consistent map.
+#[derive(Clone)]
+pub struct PcsConfigStore {
+    inner: Arc<RwLock<HashMap<String, (StPCSDataBytePosInAllDataCfg, Vec<u8>)>>>,
+}
+
+impl PcsConfigStore {
+    /// Load the store from `path`, same format as `load_pcs_alldata_config`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let map = load_pcs_alldata_config(path)?;
+        Ok(Self {
+            inner: Arc::new(RwLock::new(map)),
+        })
+    }
+
+    /// Get a clone of the config entry for `pcstype`, if present.
+    pub fn get(&self, pcstype: &str) -> Option<(StPCSDataBytePosInAllDataCfg, Vec<u8>)> {
+        self.inner.read().unwrap().get(pcstype).cloned()
+    }
+
+    /// Insert or update the config entry for `pcstype`. Re-validates before storing.
+    pub fn insert(&self, config: StPCSDataBytePosInAllDataCfg) -> Result<()> {
+        validate_pcs_alldata_config(&config)?;
+        let pcstype = config.pcstype.clone();
+        let status_values: Vec<u8> = config.pcs_controllable_status_value.values().copied().collect();
+        self.inner
+            .write()
+            .unwrap()
+            .insert(pcstype.clone(), (config, status_values));
+        info!("PcsConfigStore: upserted config for PCS type '{}'", pcstype);
+        Ok(())
+    }
+
+    /// Remove the config entry for `pcstype`, returning it if it existed.
+    pub fn remove(&self, pcstype: &str) -> Option<(StPCSDataBytePosInAllDataCfg, Vec<u8>)> {
+        let removed = self.inner.write().unwrap().remove(pcstype);
+        if removed.is_some() {
+            info!("PcsConfigStore: removed config for PCS type '{}'", pcstype);
+        }
+        removed
+    }
+
+    /// Persist the current map back to `path` as JSON, same shape `load_pcs_alldata_config` reads.
+    pub fn persist<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let configs: Vec<StPCSDataBytePosInAllDataCfg> = self
+            .inner
+            .read()
+            .unwrap()
+            .values()
+            .map(|(cfg, _)| cfg.clone())
+            .collect();
+        let file = File::create(&path)
+            .with_context(|| format!("Failed to create PCS allData config file '{:?}'", path.as_ref()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &configs)
+            .with_context(|| format!("Failed to serialize PCS allData config to '{:?}'", path.as_ref()))?;
+        info!("PcsConfigStore: persisted {} config(s) to '{:?}'", configs.len(), path.as_ref());
+        Ok(())
+    }
+
+    /// Re-read `path`, re-validate every entry, and atomically swap the live map so
+    /// in-flight `get_info` calls always observe either the old or the new config, never a mix.
+    pub fn reload<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let new_map = load_pcs_alldata_config(path)?;
+        *self.inner.write().unwrap() = new_map;
+        info!("PcsConfigStore: reloaded configuration");
+        Ok(())
+    }
+}
+
 /// Calculate total quantity of all PCS devices across all types
 /// 
 /// Iterates through the configuration vector and sums up the `quantityofthistype`
@@ -261,6 +425,49 @@ impl StPCSinfo {
     /// Sentinel value indicating invalid/missing data
     pub const INVALID_VALUE: f32 = 999999.0;
 
+    /// Decode a single measurand from `alldata` per `desc.kind`, applying
+    /// `raw * desc.scale + desc.offset`, falling back to `INVALID_VALUE` on an
+    /// out-of-range position or a type mismatch (pushing a human-readable warning).
+    fn decode_measurand(
+        alldata: &[crate::goose::types::IECData],
+        desc: &FieldDescriptor,
+        label: &str,
+        lan_id: u8,
+        logical_id: u16,
+        warnings: &mut Vec<String>,
+    ) -> f32 {
+        match alldata.get(desc.pos) {
+            Some(value) => {
+                let raw = match desc.kind {
+                    FieldKind::Float32 => value.as_f32(),
+                    FieldKind::Int16 => value.as_i16().map(|v| v as f32),
+                    FieldKind::Int32 | FieldKind::ScaledInt => value
+                        .as_i32()
+                        .map(|v| v as f32)
+                        .or_else(|| value.as_i16().map(|v| v as f32)),
+                    FieldKind::Bool => value.as_bool().map(|b| if b { 1.0 } else { 0.0 }),
+                };
+                match raw {
+                    Some(raw) => raw * desc.scale + desc.offset,
+                    None => {
+                        warnings.push(format!(
+                            "lan{} PCS{} {}: wrong type at position {}, expected {:?}, got {}",
+                            lan_id, logical_id, label, desc.pos, desc.kind, value.variant_name()
+                        ));
+                        Self::INVALID_VALUE
+                    }
+                }
+            }
+            None => {
+                warnings.push(format!(
+                    "lan{} PCS{} {}: position {} out of bounds (allData length: {})",
+                    lan_id, logical_id, label, desc.pos, alldata.len()
+                ));
+                Self::INVALID_VALUE
+            }
+        }
+    }
+
     /// Populate StPCSinfo from SubscriberPCSData using configuration mappings
     /// 
     /// # Arguments
@@ -309,116 +516,32 @@ impl StPCSinfo {
         let mut warnings = Vec::new();
         
         // Extract realtime active power
-        match alldata.get(config.pcs_realtime_active_power_pos) {
-            Some(value) => {
-                self.pcs_realtime_active_power = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} Active power: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_realtime_active_power_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} Active power: position {} out of bounds (allData length: {})", 
-                    lan_id,logical_id, config.pcs_realtime_active_power_pos, alldata.len()));
-                self.pcs_realtime_active_power = Self::INVALID_VALUE;
-            }
-        }
-        
+        self.pcs_realtime_active_power = Self::decode_measurand(
+            alldata, &config.pcs_realtime_active_power_pos, "Active power", lan_id, logical_id, &mut warnings);
+
         // Extract realtime reactive power
-        match alldata.get(config.pcs_realtime_reactive_power_pos) {
-            Some(value) => {
-                self.pcs_realtime_reactive_power = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} Reactive power: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_realtime_reactive_power_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} Reactive power: position {} out of bounds (allData length: {})", 
-                    lan_id,logical_id, config.pcs_realtime_reactive_power_pos, alldata.len()));
-                self.pcs_realtime_reactive_power = Self::INVALID_VALUE;
-            }
-        }
-        
+        self.pcs_realtime_reactive_power = Self::decode_measurand(
+            alldata, &config.pcs_realtime_reactive_power_pos, "Reactive power", lan_id, logical_id, &mut warnings);
+
         // Extract maximum charging power
-        match alldata.get(config.pcs_maximum_charging_power_pos) {
-            Some(value) => {
-                self.pcs_maximum_charging_power = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} Max charging power: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_maximum_charging_power_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} Max charging power: position {} out of bounds (allData length: {})", 
-                    lan_id,logical_id, config.pcs_maximum_charging_power_pos, alldata.len()));
-                self.pcs_maximum_charging_power = Self::INVALID_VALUE;
-            }
-        }
-        
+        self.pcs_maximum_charging_power = Self::decode_measurand(
+            alldata, &config.pcs_maximum_charging_power_pos, "Max charging power", lan_id, logical_id, &mut warnings);
+
         // Extract maximum discharging power
-        match alldata.get(config.pcs_maximum_discharging_power_pos) {
-            Some(value) => {
-                self.pcs_maximum_discharging_power = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} Max discharging power: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_maximum_discharging_power_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} Max discharging power: position {} out of bounds (allData length: {})", 
-                    lan_id,logical_id, config.pcs_maximum_discharging_power_pos, alldata.len()));
-                self.pcs_maximum_discharging_power = Self::INVALID_VALUE;
-            }
-        }
-        
+        self.pcs_maximum_discharging_power = Self::decode_measurand(
+            alldata, &config.pcs_maximum_discharging_power_pos, "Max discharging power", lan_id, logical_id, &mut warnings);
+
         // Extract maximum inductive power
-        match alldata.get(config.pcs_maximum_inductive_power_pos) {
-            Some(value) => {
-                self.pcs_maximum_inductive_power = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} Max inductive power: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_maximum_inductive_power_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} Max inductive power: position {} out of bounds (allData length: {})", 
-                    lan_id,logical_id, config.pcs_maximum_inductive_power_pos, alldata.len()));
-                self.pcs_maximum_inductive_power = Self::INVALID_VALUE;
-            }
-        }
-        
+        self.pcs_maximum_inductive_power = Self::decode_measurand(
+            alldata, &config.pcs_maximum_inductive_power_pos, "Max inductive power", lan_id, logical_id, &mut warnings);
+
         // Extract maximum capacitive power
-        match alldata.get(config.pcs_maximum_capacitive_power_pos) {
-            Some(value) => {
-                self.pcs_maximum_capacitive_power = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} Max capacitive power: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_maximum_capacitive_power_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} Max capacitive power: position {} out of bounds (allData length: {})", 
-                    lan_id,logical_id, config.pcs_maximum_capacitive_power_pos, alldata.len()));
-                self.pcs_maximum_capacitive_power = Self::INVALID_VALUE;
-            }
-        }
+        self.pcs_maximum_capacitive_power = Self::decode_measurand(
+            alldata, &config.pcs_maximum_capacitive_power_pos, "Max capacitive power", lan_id, logical_id, &mut warnings);
 
         // Extract State of Charge (SOC)
-        match alldata.get(config.pcs_soc_pos) {
-            Some(value) => {
-                self.pcs_soc = value.as_f32().unwrap_or_else(|| {
-                    warnings.push(format!("lan{} PCS{} SOC: wrong type at position {}, expected float32, got {}", 
-                        lan_id,logical_id, config.pcs_soc_pos, value.variant_name()));
-                    Self::INVALID_VALUE
-                });
-            }
-            None => {
-                warnings.push(format!("lan{} PCS{} SOC: position {} out of bounds (allData length: {})",    
-                    lan_id,logical_id, config.pcs_soc_pos, alldata.len()));
-                self.pcs_soc = Self::INVALID_VALUE;
-            }
-        }   
+        self.pcs_soc = Self::decode_measurand(
+            alldata, &config.pcs_soc_pos, "SOC", lan_id, logical_id, &mut warnings);
 
         // Extract pcs status (comes as float, convert to integer)
         match alldata.get(config.pcs_status_pos) {
@@ -481,9 +604,24 @@ impl StPCSinfo {
         //     // }
         // }
         
-        // info!("Extracted PCS info from lan{} for logical_id {} (type: {}): active_power={}, reactive_power={}", 
+        // info!("Extracted PCS info from lan{} for logical_id {} (type: {}): active_power={}, reactive_power={}",
         //       lan_id, logical_id, pcstype, self.pcs_realtime_active_power, self.pcs_realtime_reactive_power);
-        
+
+        // `is_data_valid()` already folds in GOOSE liveness (timeAllowedToLive *
+        // TAL_STALENESS_MULTIPLIER elapsed since the last accepted frame), so a
+        // silent publisher lands here with is_valid == 0. Don't let the last-known
+        // measurands keep reporting as if they were still current.
+        if self.is_valid == 0 {
+            self.pcs_realtime_active_power = Self::INVALID_VALUE;
+            self.pcs_realtime_reactive_power = Self::INVALID_VALUE;
+            self.pcs_maximum_charging_power = Self::INVALID_VALUE;
+            self.pcs_maximum_discharging_power = Self::INVALID_VALUE;
+            self.pcs_maximum_inductive_power = Self::INVALID_VALUE;
+            self.pcs_maximum_capacitive_power = Self::INVALID_VALUE;
+            self.pcs_soc = Self::INVALID_VALUE;
+            self.is_controllable = 0;
+        }
+
         Ok(())
     }
 }
@@ -1,11 +1,24 @@
 // recieve goose commnad from PMS and mapping command to each pcs. acitve power enable /disable, reactive power enable/disable, acitve power setpoint, reacitve power set point etc.
 
-use crate::goose::types::IECGoosePdu;
+use crate::goose::types::{IECData, IECGoosePdu};
 use crate::pcs::nameplate::NameplateConfig;
 use anyhow::Result;
-use log::{error, info};
+use dashmap::DashMap;
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// How much slack to give a PMS command publisher's advertised `timeAllowedToLive`
+/// before failing safe, to tolerate normal network/scheduling jitter.
+pub const PMS_TAL_MULTIPLIER: u32 = 2;
+
+/// How soon after accepting a frame on one LAN an identical (same APPID,
+/// `stNum`, `sqNum`) frame arriving on the *other* LAN is counted as the
+/// redundant PRP/HSR-style copy of that same frame, rather than a stale
+/// retransmission that happens to collide. See
+/// [`PmsGooseCmdSubscriber::redundant_copy_count`].
+pub const REDUNDANT_COPY_WINDOW: Duration = Duration::from_millis(500);
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +27,13 @@ pub struct PmsConfig {
     pub pms_command_appid_list: Vec<u16>, // list of APPIDs to subscribe to PMS GOOSE commands  convert to u16 when used.
     pub pms_command_pcs_mapping: HashMap<u16, Vec<u16>>, // mapping from PMS GOOSE APPID to list of PCS IDs <command_appid, vec![pcs_logical_id1, pcs_logical_  id2,...]>
                                                          // the default mapping is: boolean_enable_active_power_control_pcs1, boolean_enable_reactive_power_control_pcs1,etc... pcs_n, float_active_power_setpoint_pcs1, float_reactive_power_setpoint_pcs1,etc... pcs_n.
+    pub pms_command_schema: HashMap<u16, PmsCommandSchema>, // per-APPID allData layout used to position-decode and validate frames
+    /// Grace factor applied to a publisher's advertised `timeAllowedtoLive`
+    /// before a subscriber is declared stale; see [`PMS_TAL_MULTIPLIER`].
+    pub tal_multiplier: u32,
+    /// What to do to a PCS's commanded setpoints when its PMS subscription
+    /// times out; see [`PmsFailsafeAction`].
+    pub failsafe_action: PmsFailsafeAction,
 }
 
 // create PmsConfig instance from NamplateConfig vecotr
@@ -79,13 +99,132 @@ impl PmsConfig {
             );
         }
 
+        // Every APPID gets the legacy fixed layout by default, matching the
+        // grouping `pms_command_pcs_mapping`'s doc comment already describes.
+        // A deployment with a non-default dataset order can override an
+        // individual APPID's entry after construction.
+        let pms_command_schema: HashMap<u16, PmsCommandSchema> = pms_command_pcs_mapping
+            .iter()
+            .map(|(&appid, pcs_ids)| (appid, PmsCommandSchema::legacy(pcs_ids.len())))
+            .collect();
+
         Ok(PmsConfig {
             pms_command_appid_list,
             pms_command_pcs_mapping,
+            pms_command_schema,
+            tal_multiplier: PMS_TAL_MULTIPLIER,
+            failsafe_action: PmsFailsafeAction::default(),
         })
     }
 }
 
+/// What a PCS's commanded setpoints should do once its PMS GOOSE subscription
+/// is declared stale (see [`PmsGooseCmdSubscriber::sweep_invalidity`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PmsFailsafeAction {
+    /// Leave the last commanded setpoints and enables in place.
+    #[default]
+    HoldLastValue,
+    /// Zero both power setpoints, leaving the enable flags untouched.
+    ZeroSetpoints,
+    /// Clear both power control enable flags, leaving setpoints untouched.
+    Disable,
+}
+
+impl PmsFailsafeAction {
+    fn apply(&self, values: &mut PcsCommandValues) {
+        match self {
+            PmsFailsafeAction::HoldLastValue => {}
+            PmsFailsafeAction::ZeroSetpoints => {
+                values.active_power_setpoint = 0.0;
+                values.reactive_power_setpoint = 0.0;
+            }
+            PmsFailsafeAction::Disable => {
+                values.active_power_control_enable = false;
+                values.reactive_power_control_enable = false;
+            }
+        }
+    }
+}
+
+/// One kind of command value a [`PmsCommandSchema`] can place at a given
+/// `allData` position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PmsCommandFieldKind {
+    ActivePowerEnable,
+    ReactivePowerEnable,
+    ActivePowerSetpoint,
+    ReactivePowerSetpoint,
+    ModeSelector,
+}
+
+/// One `allData` position paired with the PCS it targets (by index into this
+/// APPID's `pms_command_pcs_mapping` entry, not `logical_id`) and the field
+/// it supplies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PmsCommandField {
+    pub pcs_index: usize,
+    pub kind: PmsCommandFieldKind,
+}
+
+/// Per-APPID schema describing every `allData` position's type and target
+/// PCS, so `apply_pms_command` can decode by position and validate a frame
+/// instead of assuming its shape. `entries[i]` describes `allData[i]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PmsCommandSchema {
+    pub entries: Vec<PmsCommandField>,
+}
+
+impl PmsCommandSchema {
+    /// The layout `apply_pms_command` hardcoded before this was data-driven:
+    /// `N` active-power enables, then `N` reactive-power enables, then `N`
+    /// active setpoints, then `N` reactive setpoints, one per PCS in
+    /// `pcs_logical_ids` order.
+    pub fn legacy(pcs_count: usize) -> Self {
+        let mut entries = Vec::with_capacity(pcs_count * 4);
+        for kind in [
+            PmsCommandFieldKind::ActivePowerEnable,
+            PmsCommandFieldKind::ReactivePowerEnable,
+            PmsCommandFieldKind::ActivePowerSetpoint,
+            PmsCommandFieldKind::ReactivePowerSetpoint,
+        ] {
+            for pcs_index in 0..pcs_count {
+                entries.push(PmsCommandField { pcs_index, kind });
+            }
+        }
+        PmsCommandSchema { entries }
+    }
+
+    /// Does `alldata` have exactly one entry per schema position, each of
+    /// the type that position's `kind` requires?
+    fn validate(&self, alldata: &[IECData]) -> std::result::Result<(), String> {
+        if alldata.len() != self.entries.len() {
+            return Err(format!(
+                "expected {} allData entries, got {}",
+                self.entries.len(),
+                alldata.len()
+            ));
+        }
+        for (i, field) in self.entries.iter().enumerate() {
+            let type_matches = match field.kind {
+                PmsCommandFieldKind::ActivePowerEnable | PmsCommandFieldKind::ReactivePowerEnable => {
+                    alldata[i].as_bool().is_some()
+                }
+                PmsCommandFieldKind::ActivePowerSetpoint | PmsCommandFieldKind::ReactivePowerSetpoint => {
+                    alldata[i].as_f32().is_some()
+                }
+                PmsCommandFieldKind::ModeSelector => alldata[i].as_i32().is_some(),
+            };
+            if !type_matches {
+                return Err(format!("allData[{}] has the wrong type for {:?}", i, field.kind));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct PmsGooseCmdSubscriber {
@@ -95,4 +234,470 @@ pub struct PmsGooseCmdSubscriber {
     pub last_update_time: Option<std::time::SystemTime>, // timestamp of last update
     pub invalidity_time: Option<std::time::SystemTime>, // timestamp of invalidity time used for timeout detection
     pub invalid: bool, // flag to indicate if the command is invalid
+    tal_multiplier: u32, // grace factor on the publisher's advertised timeAllowedtoLive; see PmsConfig::tal_multiplier
+    last_accepted_lan: Option<u16>, // which LAN the currently-stored goosepdu was accepted from
+    /// How many times the redundant LAN has delivered an identical copy of
+    /// the currently-stored frame within [`REDUNDANT_COPY_WINDOW`] of it
+    /// being accepted. Confirms the standby path is alive without being
+    /// treated as a command change; see [`Self::accept_frame`].
+    pub redundant_copy_count: u64,
+}
+
+impl PmsGooseCmdSubscriber {
+    pub fn new(goose_appid: u16) -> Self {
+        Self::with_tal_multiplier(goose_appid, PMS_TAL_MULTIPLIER)
+    }
+
+    /// Same as [`Self::new`], but with an explicit grace factor instead of
+    /// [`PMS_TAL_MULTIPLIER`] (normally `PmsConfig::tal_multiplier`).
+    pub fn with_tal_multiplier(goose_appid: u16, tal_multiplier: u32) -> Self {
+        Self {
+            goose_appid,
+            goosepdu: IECGoosePdu::default(),
+            last_update_time: None,
+            invalidity_time: None,
+            invalid: true, // no frame received yet: fail-safe until the first accepted frame
+            tal_multiplier,
+            last_accepted_lan: None,
+            redundant_copy_count: 0,
+        }
+    }
+
+    /// Apply a freshly decoded PMS GOOSE frame, received on `lan_id`, per IEC
+    /// 61850 freshness rules.
+    ///
+    /// An exact duplicate (same `stNum`/`sqNum` as already stored) is ignored
+    /// entirely and returns `false`. If it arrived on the LAN other than the
+    /// one the stored frame was accepted from, and within
+    /// [`REDUNDANT_COPY_WINDOW`] of that acceptance, it's counted in
+    /// [`Self::redundant_copy_count`] -- the redundant PRP/HSR-style copy of
+    /// the same frame, confirming that path is alive -- rather than as a
+    /// stale collision. Otherwise the frame is accepted: `goosepdu`,
+    /// `last_update_time` and `invalidity_time` (`last_update_time + tal_multiplier
+    /// * timeAllowedToLive`) are refreshed and `invalid` is cleared. A decreasing
+    /// `stNum` is treated as a publisher restart (accepted, re-synced). Returns
+    /// `true` only when this is a real command change (a new or restarted `stNum`)
+    /// that callers should fan out to the mapped PCS units -- a mere retransmission
+    /// (`sqNum` bump, same `stNum`) refreshes liveness but yields `false`.
+    pub fn accept_frame(&mut self, lan_id: u16, pdu: &IECGoosePdu) -> bool {
+        self.accept_frame_at(lan_id, pdu, SystemTime::now())
+    }
+
+    /// Same as [`Self::accept_frame`], but stamps `last_update_time`/
+    /// `invalidity_time` from `clock` (PTP-disciplined where synchronized)
+    /// instead of undisciplined `SystemTime::now()`.
+    pub fn accept_frame_ptp(&mut self, lan_id: u16, pdu: &IECGoosePdu, clock: &crate::network::ptp_clock::PtpClock) -> bool {
+        self.accept_frame_at(lan_id, pdu, clock.now().0)
+    }
+
+    fn accept_frame_at(&mut self, lan_id: u16, pdu: &IECGoosePdu, now: SystemTime) -> bool {
+        let first_frame = self.last_update_time.is_none();
+        let current_stnum = self.goosepdu.stNum;
+        let current_sqnum = self.goosepdu.sqNum;
+
+        let is_restart = !first_frame && pdu.stNum < current_stnum;
+        let is_change = first_frame || pdu.stNum > current_stnum || is_restart;
+        let is_retransmission = !is_change && pdu.stNum == current_stnum && pdu.sqNum > current_sqnum;
+        let is_exact_duplicate = !first_frame && pdu.stNum == current_stnum && pdu.sqNum == current_sqnum;
+
+        if is_exact_duplicate && self.last_accepted_lan.is_some_and(|last_lan| last_lan != lan_id) {
+            let within_window = self
+                .last_update_time
+                .and_then(|last| now.duration_since(last).ok())
+                .is_some_and(|elapsed| elapsed <= REDUNDANT_COPY_WINDOW);
+            if within_window {
+                self.redundant_copy_count += 1;
+            }
+        }
+
+        if !is_change && !is_retransmission {
+            return false;
+        }
+
+        if is_restart {
+            warn!(
+                "PMS GOOSE APPID 0x{:04X}: publisher restart detected (stNum {} -> {}), re-syncing",
+                self.goose_appid, current_stnum, pdu.stNum
+            );
+        }
+
+        self.goosepdu = pdu.clone();
+        self.last_update_time = Some(now);
+        self.invalidity_time =
+            Some(now + Duration::from_millis(pdu.timeAllowedtoLive as u64 * self.tal_multiplier as u64));
+        self.invalid = false;
+        self.last_accepted_lan = Some(lan_id);
+
+        is_change
+    }
+
+    /// Fail-safe watchdog: if no frame has arrived before `invalidity_time`, mark
+    /// this subscriber invalid so callers stop applying its last-known command
+    /// values. Returns `true` exactly on the transition into invalid.
+    pub fn sweep_invalidity(&mut self) -> bool {
+        if self.invalid {
+            return false;
+        }
+        match self.invalidity_time {
+            Some(deadline) if SystemTime::now() >= deadline => {
+                warn!(
+                    "PMS GOOSE APPID 0x{:04X}: no frame before invalidity_time, failing safe",
+                    self.goose_appid
+                );
+                self.invalid = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Command values decoded from a PMS GOOSE frame for a single PCS logical id.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PcsCommandValues {
+    pub active_power_control_enable: bool,
+    pub reactive_power_control_enable: bool,
+    pub active_power_setpoint: f32,
+    pub reactive_power_setpoint: f32,
+    pub mode_selector: i32,
+}
+
+/// Command state fanned out from accepted PMS GOOSE frames, keyed by PCS
+/// `logical_id`. Downstream publishers read this to decide what to encode
+/// into that PCS's own published GOOSE `allData`.
+pub type PcsCommandTable = DashMap<u16, PcsCommandValues>;
+
+/// Apply `action` to every PCS in `pcs_logical_ids` in `commands`, called on
+/// the edge where `PmsGooseCmdSubscriber::sweep_invalidity` transitions a PMS
+/// subscription (`goose_appid`) into timeout. A PCS with no entry yet (never
+/// commanded) is left absent rather than seeded with a failsafe-shaped
+/// default, since there's no prior command to fail safe from.
+pub fn apply_pms_failsafe(
+    goose_appid: u16,
+    pcs_logical_ids: &[u16],
+    action: PmsFailsafeAction,
+    commands: &PcsCommandTable,
+) {
+    for &pcs_id in pcs_logical_ids {
+        if let Some(mut entry) = commands.get_mut(&pcs_id) {
+            info!(
+                "PMS GOOSE APPID 0x{:04X}: applying failsafe action {:?} to PCS {}",
+                goose_appid, action, pcs_id
+            );
+            action.apply(&mut entry);
+        }
+    }
+}
+
+/// Decode `allData` for an accepted PMS GOOSE frame and fan the values out to
+/// `pcs_logical_ids` into `commands`, per `schema`'s position-driven layout
+/// (`schema.entries[i]` names the PCS index and field kind `allData[i]`
+/// supplies). `schema` is validated against `alldata` first - a count or
+/// type mismatch logs a single warning and the whole frame is rejected
+/// (no partial apply), rather than the previous per-field "missing/invalid"
+/// warnings with the rest of the frame still applied.
+pub fn apply_pms_command(
+    goose_appid: u16,
+    alldata: &[IECData],
+    pcs_logical_ids: &[u16],
+    schema: &PmsCommandSchema,
+    commands: &PcsCommandTable,
+) {
+    if let Err(reason) = schema.validate(alldata) {
+        warn!(
+            "PMS GOOSE APPID 0x{:04X}: allData does not match the configured command schema, rejecting frame ({})",
+            goose_appid, reason
+        );
+        return;
+    }
+
+    for (i, field) in schema.entries.iter().enumerate() {
+        let Some(&pcs_id) = pcs_logical_ids.get(field.pcs_index) else {
+            warn!(
+                "PMS GOOSE APPID 0x{:04X}: schema entry {} references PCS index {}, out of range of {} mapped PCS units",
+                goose_appid, i, field.pcs_index, pcs_logical_ids.len()
+            );
+            continue;
+        };
+        let mut entry = commands.entry(pcs_id).or_insert_with(PcsCommandValues::default);
+
+        // Type already confirmed by schema.validate(), so these are infallible.
+        match field.kind {
+            PmsCommandFieldKind::ActivePowerEnable => {
+                entry.active_power_control_enable = alldata[i].as_bool().unwrap_or_default();
+            }
+            PmsCommandFieldKind::ReactivePowerEnable => {
+                entry.reactive_power_control_enable = alldata[i].as_bool().unwrap_or_default();
+            }
+            PmsCommandFieldKind::ActivePowerSetpoint => {
+                entry.active_power_setpoint = alldata[i].as_f32().unwrap_or_default();
+            }
+            PmsCommandFieldKind::ReactivePowerSetpoint => {
+                entry.reactive_power_setpoint = alldata[i].as_f32().unwrap_or_default();
+            }
+            PmsCommandFieldKind::ModeSelector => {
+                entry.mode_selector = alldata[i].as_i32().unwrap_or_default();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_with_mode_selector() -> PmsCommandSchema {
+        let mut schema = PmsCommandSchema::legacy(2);
+        schema.entries.push(PmsCommandField {
+            pcs_index: 0,
+            kind: PmsCommandFieldKind::ModeSelector,
+        });
+        schema
+    }
+
+    #[test]
+    fn test_legacy_schema_orders_entries_by_field_group_then_pcs() {
+        let schema = PmsCommandSchema::legacy(2);
+        assert_eq!(schema.entries.len(), 8);
+        assert_eq!(schema.entries[0].kind, PmsCommandFieldKind::ActivePowerEnable);
+        assert_eq!(schema.entries[1].kind, PmsCommandFieldKind::ActivePowerEnable);
+        assert_eq!(schema.entries[2].kind, PmsCommandFieldKind::ReactivePowerEnable);
+        assert_eq!(schema.entries[6].kind, PmsCommandFieldKind::ReactivePowerSetpoint);
+        assert_eq!(schema.entries[0].pcs_index, 0);
+        assert_eq!(schema.entries[1].pcs_index, 1);
+    }
+
+    #[test]
+    fn test_apply_pms_command_decodes_legacy_layout() {
+        let schema = PmsCommandSchema::legacy(2);
+        let alldata = vec![
+            IECData::boolean(true),
+            IECData::boolean(false),
+            IECData::boolean(false),
+            IECData::boolean(true),
+            IECData::float32(10.5),
+            IECData::float32(20.5),
+            IECData::float32(1.0),
+            IECData::float32(2.0),
+        ];
+        let commands = PcsCommandTable::new();
+        apply_pms_command(0x1234, &alldata, &[100, 101], &schema, &commands);
+
+        let pcs100 = commands.get(&100).unwrap();
+        assert!(pcs100.active_power_control_enable);
+        assert!(!pcs100.reactive_power_control_enable);
+        assert_eq!(pcs100.active_power_setpoint, 10.5);
+        assert_eq!(pcs100.reactive_power_setpoint, 1.0);
+
+        let pcs101 = commands.get(&101).unwrap();
+        assert!(!pcs101.active_power_control_enable);
+        assert!(pcs101.reactive_power_control_enable);
+        assert_eq!(pcs101.active_power_setpoint, 20.5);
+        assert_eq!(pcs101.reactive_power_setpoint, 2.0);
+    }
+
+    #[test]
+    fn test_apply_pms_command_decodes_mode_selector() {
+        let schema = schema_with_mode_selector();
+        let mut alldata = vec![
+            IECData::boolean(true),
+            IECData::boolean(false),
+            IECData::boolean(false),
+            IECData::boolean(true),
+            IECData::float32(10.5),
+            IECData::float32(20.5),
+            IECData::float32(1.0),
+            IECData::float32(2.0),
+        ];
+        alldata.push(IECData::int32(3));
+        let commands = PcsCommandTable::new();
+        apply_pms_command(0x1234, &alldata, &[100, 101], &schema, &commands);
+
+        assert_eq!(commands.get(&100).unwrap().mode_selector, 3);
+    }
+
+    #[test]
+    fn test_apply_pms_command_rejects_wrong_item_count() {
+        let schema = PmsCommandSchema::legacy(2);
+        let alldata = vec![IECData::boolean(true)];
+        let commands = PcsCommandTable::new();
+        apply_pms_command(0x1234, &alldata, &[100, 101], &schema, &commands);
+
+        assert!(commands.get(&100).is_none());
+    }
+
+    #[test]
+    fn test_apply_pms_command_rejects_wrong_item_type() {
+        let schema = PmsCommandSchema::legacy(2);
+        let mut alldata = vec![
+            IECData::boolean(true),
+            IECData::boolean(false),
+            IECData::boolean(false),
+            IECData::boolean(true),
+            IECData::float32(10.5),
+            IECData::float32(20.5),
+            IECData::float32(1.0),
+            IECData::float32(2.0),
+        ];
+        // Corrupt one entry's type: schema expects a float here, not a bool.
+        alldata[4] = IECData::boolean(true);
+        let commands = PcsCommandTable::new();
+        apply_pms_command(0x1234, &alldata, &[100, 101], &schema, &commands);
+
+        assert!(commands.get(&100).is_none());
+    }
+
+    #[test]
+    fn test_sweep_invalidity_transitions_once_past_deadline() {
+        let mut sub = PmsGooseCmdSubscriber::with_tal_multiplier(0x1234, 1);
+        let pdu = IECGoosePdu {
+            timeAllowedtoLive: 0, // already-elapsed window: any sweep after accept is past deadline
+            ..Default::default()
+        };
+        assert!(sub.accept_frame(1, &pdu));
+        assert!(!sub.invalid);
+
+        assert!(sub.sweep_invalidity(), "first sweep past the deadline should transition to invalid");
+        assert!(sub.invalid);
+        assert!(!sub.sweep_invalidity(), "already invalid: no further transition");
+    }
+
+    #[test]
+    fn test_sweep_invalidity_clears_on_resumed_frames() {
+        let mut sub = PmsGooseCmdSubscriber::with_tal_multiplier(0x1234, 1);
+        let stale_pdu = IECGoosePdu {
+            timeAllowedtoLive: 0,
+            ..Default::default()
+        };
+        sub.accept_frame(1, &stale_pdu);
+        assert!(sub.sweep_invalidity());
+        assert!(sub.invalid);
+
+        let fresh_pdu = IECGoosePdu {
+            stNum: 2,
+            timeAllowedtoLive: 60_000,
+            ..Default::default()
+        };
+        sub.accept_frame(1, &fresh_pdu);
+        assert!(!sub.invalid);
+    }
+
+    #[test]
+    fn test_redundant_lan_copy_within_window_is_counted_not_applied() {
+        let mut sub = PmsGooseCmdSubscriber::with_tal_multiplier(0x1234, 1);
+        let pdu = IECGoosePdu {
+            stNum: 1,
+            sqNum: 1,
+            timeAllowedtoLive: 60_000,
+            ..Default::default()
+        };
+        assert!(sub.accept_frame(1, &pdu), "first frame on LAN1 is a change");
+        assert_eq!(sub.redundant_copy_count, 0);
+
+        // Identical frame arrives moments later on the standby LAN2 path.
+        assert!(!sub.accept_frame(2, &pdu), "redundant copy is not a command change");
+        assert_eq!(sub.redundant_copy_count, 1, "redundant copy on the other LAN should be counted");
+        assert_eq!(sub.goosepdu.stNum, 1, "stored frame is unaffected by the redundant copy");
+    }
+
+    #[test]
+    fn test_identical_frame_on_same_lan_is_not_counted_as_redundant() {
+        let mut sub = PmsGooseCmdSubscriber::with_tal_multiplier(0x1234, 1);
+        let pdu = IECGoosePdu {
+            stNum: 1,
+            sqNum: 1,
+            timeAllowedtoLive: 60_000,
+            ..Default::default()
+        };
+        assert!(sub.accept_frame(1, &pdu));
+        assert!(!sub.accept_frame(1, &pdu), "re-delivery on the same LAN is an ordinary duplicate");
+        assert_eq!(sub.redundant_copy_count, 0, "only the *other* LAN confirms standby-path liveness");
+    }
+
+    #[test]
+    fn test_retransmission_on_other_lan_is_not_counted_as_redundant_copy() {
+        let mut sub = PmsGooseCmdSubscriber::with_tal_multiplier(0x1234, 1);
+        let pdu = IECGoosePdu {
+            stNum: 1,
+            sqNum: 1,
+            timeAllowedtoLive: 60_000,
+            ..Default::default()
+        };
+        assert!(sub.accept_frame(1, &pdu));
+
+        let retransmission = IECGoosePdu {
+            stNum: 1,
+            sqNum: 2,
+            timeAllowedtoLive: 60_000,
+            ..Default::default()
+        };
+        assert!(!sub.accept_frame(2, &retransmission), "sqNum bump alone is not a command change");
+        assert_eq!(sub.redundant_copy_count, 0, "differing sqNum means this isn't an identical copy");
+        assert_eq!(sub.goosepdu.sqNum, 2, "retransmission still refreshes the stored frame/liveness");
+    }
+
+    #[test]
+    fn test_apply_pms_failsafe_zero_setpoints_only_touches_setpoints() {
+        let commands = PcsCommandTable::new();
+        commands.insert(
+            100,
+            PcsCommandValues {
+                active_power_control_enable: true,
+                reactive_power_control_enable: true,
+                active_power_setpoint: 42.0,
+                reactive_power_setpoint: 7.0,
+                mode_selector: 3,
+            },
+        );
+        apply_pms_failsafe(0x1234, &[100], PmsFailsafeAction::ZeroSetpoints, &commands);
+
+        let values = commands.get(&100).unwrap();
+        assert_eq!(values.active_power_setpoint, 0.0);
+        assert_eq!(values.reactive_power_setpoint, 0.0);
+        assert!(values.active_power_control_enable);
+    }
+
+    #[test]
+    fn test_apply_pms_failsafe_disable_only_touches_enables() {
+        let commands = PcsCommandTable::new();
+        commands.insert(
+            100,
+            PcsCommandValues {
+                active_power_control_enable: true,
+                reactive_power_control_enable: true,
+                active_power_setpoint: 42.0,
+                reactive_power_setpoint: 7.0,
+                mode_selector: 0,
+            },
+        );
+        apply_pms_failsafe(0x1234, &[100], PmsFailsafeAction::Disable, &commands);
+
+        let values = commands.get(&100).unwrap();
+        assert!(!values.active_power_control_enable);
+        assert!(!values.reactive_power_control_enable);
+        assert_eq!(values.active_power_setpoint, 42.0);
+    }
+
+    #[test]
+    fn test_apply_pms_failsafe_hold_last_value_is_a_no_op() {
+        let commands = PcsCommandTable::new();
+        let original = PcsCommandValues {
+            active_power_control_enable: true,
+            reactive_power_control_enable: false,
+            active_power_setpoint: 42.0,
+            reactive_power_setpoint: 7.0,
+            mode_selector: 5,
+        };
+        commands.insert(100, original);
+        apply_pms_failsafe(0x1234, &[100], PmsFailsafeAction::HoldLastValue, &commands);
+
+        assert_eq!(*commands.get(&100).unwrap(), original);
+    }
+
+    #[test]
+    fn test_apply_pms_failsafe_skips_pcs_with_no_prior_command() {
+        let commands = PcsCommandTable::new();
+        apply_pms_failsafe(0x1234, &[999], PmsFailsafeAction::ZeroSetpoints, &commands);
+        assert!(commands.get(&999).is_none());
+    }
 }
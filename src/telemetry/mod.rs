@@ -0,0 +1,12 @@
+//! Optional MQTT telemetry/monitoring sidecar.
+//!
+//! Lets operators observe PCS real-time values and PMS command health as JSON
+//! over MQTT without sniffing the wire. Entirely optional: if no broker is
+//! configured it runs as a no-op drain so producer threads never block.
+
+pub mod mqtt;
+
+pub use mqtt::{
+    load_mqtt_telemetry_config, spawn_mqtt_telemetry_thread, MqttTelemetryConfig,
+    PcsTelemetrySample, PmsStatusSample, TelemetryEvent,
+};
@@ -0,0 +1,168 @@
+// Optional MQTT telemetry sidecar: publishes per-PCS real-time values and PMS
+// command health as JSON so operators can observe the simulator without
+// sniffing the wire. Fed by a crossbeam_channel from the retransmit/subscriber
+// threads so publishing a slow/unreachable broker never blocks GOOSE timing.
+
+use anyhow::{Context, Result};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use log::{info, warn};
+use rumqttc::{Client, MqttOptions, QoS};
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const INITIAL_RECONNECT_BACKOFF_MS: u64 = 500;
+const MAX_RECONNECT_BACKOFF_MS: u64 = 30_000;
+const MQTT_KEEP_ALIVE_SECS: u64 = 30;
+const MQTT_CHANNEL_CAPACITY: usize = 64;
+
+/// Broker connection settings for the telemetry sidecar, loaded from
+/// `mqtt.json` in the same config directory as `pcs.csv`. Absence of the file
+/// means "no broker configured" and the sidecar runs as a no-op.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct MqttTelemetryConfig {
+    pub broker_host: String,
+    pub broker_port: u16,
+    pub client_id: String,
+    pub publish_interval_ms: u64,
+}
+
+/// Real-time values for a single PCS, published to `pcs/<logical_id>/telemetry`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PcsTelemetrySample {
+    pub logical_id: u16,
+    pub active_power: f32,
+    pub reactive_power: f32,
+    pub active_power_control_enable: bool,
+    pub reactive_power_control_enable: bool,
+    pub st_num: u32,
+    pub sq_num: u32,
+}
+
+/// PMS command subscriber health, published to `pms/<appid>/status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PmsStatusSample {
+    pub appid: u16,
+    pub invalid: bool,
+    pub seconds_since_last_command: Option<f64>,
+}
+
+/// A sample produced by a retransmit/subscriber thread for the sidecar to publish.
+#[derive(Debug, Clone)]
+pub enum TelemetryEvent {
+    PcsTelemetry(PcsTelemetrySample),
+    PmsStatus(PmsStatusSample),
+}
+
+/// Load `mqtt.json` from `config_dir`. Returns `Ok(None)` (no-op sidecar) if
+/// the file is absent; a malformed file that does exist is still an error.
+pub fn load_mqtt_telemetry_config<P: AsRef<Path>>(config_dir: P) -> Result<Option<MqttTelemetryConfig>> {
+    let path = config_dir.as_ref().join("mqtt.json");
+    if !path.exists() {
+        info!(
+            "MQTT telemetry: no mqtt.json found at {:?}, sidecar disabled",
+            path
+        );
+        return Ok(None);
+    }
+
+    let file = File::open(&path).with_context(|| format!("Failed to open MQTT config file: {:?}", path))?;
+    let config: MqttTelemetryConfig = serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Failed to parse MQTT config JSON: {:?}", path))?;
+    info!(
+        "MQTT telemetry: configured for broker {}:{}",
+        config.broker_host, config.broker_port
+    );
+    Ok(Some(config))
+}
+
+/// Spawn the telemetry sidecar thread. If `config` is `None` the thread just
+/// drains `events_rx` so producers never block, without touching the network.
+pub fn spawn_mqtt_telemetry_thread(
+    config: Option<MqttTelemetryConfig>,
+    events_rx: Receiver<TelemetryEvent>,
+) -> JoinHandle<()> {
+    thread::spawn(move || match config {
+        Some(config) => run_telemetry_loop(config, events_rx),
+        None => {
+            info!("MQTT telemetry: sidecar running as a no-op drain (no broker configured)");
+            while events_rx.recv().is_ok() {}
+        }
+    })
+}
+
+/// Connect, publish events as they arrive, and reconnect with exponential
+/// backoff on broker loss. Returns only once `events_rx` disconnects
+/// (producer threads have shut down).
+fn run_telemetry_loop(config: MqttTelemetryConfig, events_rx: Receiver<TelemetryEvent>) {
+    let mut backoff_ms = INITIAL_RECONNECT_BACKOFF_MS;
+
+    loop {
+        let mut mqtt_options = MqttOptions::new(&config.client_id, &config.broker_host, config.broker_port);
+        mqtt_options.set_keep_alive(Duration::from_secs(MQTT_KEEP_ALIVE_SECS));
+        let (client, mut connection) = Client::new(mqtt_options, MQTT_CHANNEL_CAPACITY);
+
+        // rumqttc's blocking Client only drives its network I/O when
+        // Connection::iter() is polled, so give it its own thread to keep
+        // the publish side free.
+        let conn_thread = thread::spawn(move || {
+            for notification in connection.iter() {
+                if notification.is_err() {
+                    break;
+                }
+            }
+        });
+
+        info!(
+            "MQTT telemetry: connected to {}:{}",
+            config.broker_host, config.broker_port
+        );
+        backoff_ms = INITIAL_RECONNECT_BACKOFF_MS;
+
+        loop {
+            match events_rx.recv_timeout(Duration::from_millis(config.publish_interval_ms.max(1))) {
+                Ok(event) => {
+                    if let Err(e) = publish_event(&client, &event) {
+                        warn!("MQTT telemetry: publish failed, reconnecting: {}", e);
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("MQTT telemetry: producers shut down, stopping sidecar");
+                    drop(client);
+                    let _ = conn_thread.join();
+                    return;
+                }
+            }
+        }
+
+        drop(client);
+        let _ = conn_thread.join();
+        warn!(
+            "MQTT telemetry: disconnected from broker, retrying in {}ms",
+            backoff_ms
+        );
+        thread::sleep(Duration::from_millis(backoff_ms));
+        backoff_ms = (backoff_ms * 2).min(MAX_RECONNECT_BACKOFF_MS);
+    }
+}
+
+fn publish_event(client: &Client, event: &TelemetryEvent) -> Result<()> {
+    let (topic, payload) = match event {
+        TelemetryEvent::PcsTelemetry(sample) => (
+            format!("pcs/{}/telemetry", sample.logical_id),
+            serde_json::to_vec(sample).context("Failed to serialize PcsTelemetrySample")?,
+        ),
+        TelemetryEvent::PmsStatus(sample) => (
+            format!("pms/{}/status", sample.appid),
+            serde_json::to_vec(sample).context("Failed to serialize PmsStatusSample")?,
+        ),
+    };
+    client
+        .publish(&topic, QoS::AtMostOnce, false, payload)
+        .with_context(|| format!("Failed to publish to topic '{}'", topic))
+}
@@ -0,0 +1,203 @@
+//! Polls the nameplate CSV and `Config.toml` for modification so an operator
+//! edit takes effect without restarting the simulator, following the same
+//! poll-and-publish style as `threads::validity`'s periodic checking thread.
+
+use crate::goose::nameplate_publisher::reload_retransmit_frames;
+use crate::os::config::AppConfig;
+use crate::threads::retransmit::RetransmitFrame;
+use log::{error, info, warn};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// Default interval between modification-time checks.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Spawns a thread that polls `csv_path`'s mtime every `poll_interval` and,
+/// when it changes, re-validates and hot-reloads `frames` via
+/// `nameplate_publisher::reload_retransmit_frames`: added rows start new
+/// publishers, removed rows stop theirs, and changed rows update their live
+/// header/PDU in place. A reload that fails validation (CSV missing, or every
+/// row rejected) is logged and `frames` is left exactly as it was - the
+/// file's new mtime is still recorded as seen so a broken edit is retried
+/// only on its next save, not every poll.
+pub fn spawn_nameplate_watch_thread(
+    csv_path: PathBuf,
+    frames: Arc<RwLock<Vec<RetransmitFrame>>>,
+    poll_interval: Duration,
+    stop_signal: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_modified = file_mtime(&csv_path);
+        info!(
+            "Nameplate config watch: polling '{}' every {:?}",
+            csv_path.display(),
+            poll_interval
+        );
+
+        while !stop_signal.load(Ordering::Relaxed) {
+            thread::sleep(poll_interval);
+
+            let modified = match file_mtime(&csv_path) {
+                Some(m) => m,
+                None => {
+                    warn!("Nameplate config watch: failed to stat '{}'", csv_path.display());
+                    continue;
+                }
+            };
+            if last_modified == Some(modified) {
+                continue;
+            }
+            last_modified = Some(modified);
+
+            info!("Nameplate config watch: '{}' changed, reloading", csv_path.display());
+            let mut frames_lock = frames.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+            match reload_retransmit_frames(&csv_path, &mut frames_lock) {
+                Ok(()) => info!(
+                    "Nameplate config watch: reload applied, {} publisher(s) live",
+                    frames_lock.len()
+                ),
+                Err(e) => error!(
+                    "Nameplate config watch: reload rejected, keeping previous configuration: {}",
+                    e
+                ),
+            }
+        }
+
+        info!("Nameplate config watch: stopped");
+    })
+}
+
+/// Spawns a thread that polls `toml_path`'s mtime every `poll_interval` and,
+/// when it changes, re-parses and re-validates the configuration via
+/// `AppConfig::load`, logging the outcome either way. `AppConfig::load` reads
+/// `Config` from the working directory, not from `toml_path`, so the check
+/// covers the edited file only when `toml_path` names that same file.
+///
+/// Unlike the nameplate CSV, `AppConfig`'s fields (`goose_interface_lan1`/`lan2`)
+/// are only consumed once, to bind the LAN sockets at startup - rebinding a live
+/// `DataLinkSender` to a different interface is not something this thread
+/// attempts, so a successfully-reloaded `Config.toml` only proves the edit is
+/// valid; applying it still requires a restart. This is called out loudly
+/// rather than silently reparsing and discarding the result, so "it reloaded"
+/// in the log can't be misread as "it took effect".
+pub fn spawn_config_toml_watch_thread(
+    toml_path: PathBuf,
+    poll_interval: Duration,
+    stop_signal: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_modified = file_mtime(&toml_path);
+        info!(
+            "Config.toml watch: polling '{}' every {:?}",
+            toml_path.display(),
+            poll_interval
+        );
+
+        while !stop_signal.load(Ordering::Relaxed) {
+            thread::sleep(poll_interval);
+
+            let modified = match file_mtime(&toml_path) {
+                Some(m) => m,
+                None => {
+                    warn!("Config.toml watch: failed to stat '{}'", toml_path.display());
+                    continue;
+                }
+            };
+            if last_modified == Some(modified) {
+                continue;
+            }
+            last_modified = Some(modified);
+
+            info!("Config.toml watch: '{}' changed, re-validating", toml_path.display());
+            match AppConfig::load() {
+                Ok(_) => info!(
+                    "Config.toml watch: '{}' is valid; restart required for it to take effect",
+                    toml_path.display()
+                ),
+                Err(e) => error!(
+                    "Config.toml watch: '{}' failed validation, previous configuration remains in effect: {}",
+                    toml_path.display(),
+                    e
+                ),
+            }
+        }
+
+        info!("Config.toml watch: stopped");
+    })
+}
+
+fn file_mtime(path: &std::path::Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goose::types::{EthernetHeader, IECGoosePdu};
+    use crate::pcs::publisher::RetransmissionProfile;
+    use std::sync::atomic::AtomicU64;
+
+    fn temp_csv_path(test_name: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        std::env::temp_dir().join(format!(
+            "config_watch_{}_{}_{}.csv",
+            std::process::id(),
+            test_name,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    const CSV_HEADER: &str = "no,device_id,goose_appid,goose_srcAddr,goose_dstAddr,goose_TPID,goose_TCI,goose_gocbRef,goose_dataSet,goose_goID,goose_simulation,goose_confRev,goose_ndsCom,feed_line_id,feed_line_alias,logical_id,pcs_type,pms_appid";
+
+    fn write_csv(path: &std::path::Path, go_id: &str) {
+        let contents = format!(
+            "{header}\n1,PCS1,1,e8-d8-d1-eb-cb-b6,01-0C-CD-01-00-08,,,{go_id}$GO$Gcb,{go_id}$dsGOOSE,{go_id},false,5,false,,,1,type_a,\n",
+            header = CSV_HEADER,
+            go_id = go_id,
+        );
+        std::fs::write(path, contents).expect("write temp nameplate CSV");
+    }
+
+    #[test]
+    fn test_spawn_nameplate_watch_thread_picks_up_file_change() {
+        let path = temp_csv_path("picks_up_change");
+        write_csv(&path, "pubA");
+
+        let frames = Arc::new(RwLock::new(vec![RetransmitFrame::new(
+            EthernetHeader::default(),
+            IECGoosePdu::default(),
+            RetransmissionProfile::default(),
+        )]));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = spawn_nameplate_watch_thread(
+            path.clone(),
+            Arc::clone(&frames),
+            Duration::from_millis(20),
+            Arc::clone(&stop),
+        );
+
+        // Give the watcher a moment to take its first mtime snapshot before
+        // the file is rewritten, so the change is observable.
+        thread::sleep(Duration::from_millis(60));
+        write_csv(&path, "pubB");
+
+        let mut picked_up = false;
+        for _ in 0..50 {
+            thread::sleep(Duration::from_millis(20));
+            if frames.read().unwrap().iter().any(|f| f.pdu.goID == "pubB") {
+                picked_up = true;
+                break;
+            }
+        }
+
+        stop.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(picked_up, "watch thread should hot-reload the changed CSV");
+    }
+}
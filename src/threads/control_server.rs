@@ -0,0 +1,192 @@
+//! TCP request/response session server for injecting live PCS measurements
+//! and faults, in the per-connection read-respond-loop style of a KWP2000/
+//! ISO-TP diagnostic server: each client gets its own thread looping over
+//! newline-delimited text commands for the life of the connection, unlike
+//! `threads::validity`'s telemetry socket, which serves one snapshot and
+//! closes.
+//!
+//! Commands (one per line, space-separated fields), each answered with a
+//! single `OK ...` or `ERR <message>` response line:
+//!   `set <logical_id> <field> <value>`      - field is one of soc,
+//!                                              status, max_charging_power,
+//!                                              max_discharging_power,
+//!                                              max_capacitive_power,
+//!                                              max_inductive_power
+//!   `inject_fault <logical_id> <fault_name>` - forces status to the fault
+//!                                              code and records the name
+//!   `get <logical_id>`                       - dumps the PCS's live values
+
+use crate::pcs::live_values::PcsLiveStore;
+use log::{info, warn};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+
+/// Spawns a TCP server on `port` (all interfaces) that accepts control
+/// sessions against `store`. Each connection runs its own read/respond loop
+/// on a dedicated thread, so a slow or idle client can't block other sessions
+/// or the simulator's publishing threads, neither of which ever touch this
+/// listener.
+pub fn spawn_pcs_control_server(store: PcsLiveStore, port: u16) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let listener = match TcpListener::bind(("0.0.0.0", port)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("PCS control server: failed to bind TCP port {}: {}", port, e);
+                return;
+            }
+        };
+        info!("PCS control server: listening on port {}", port);
+
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("PCS control server: failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+            let store = store.clone();
+            thread::spawn(move || handle_session(stream, store));
+        }
+    })
+}
+
+fn handle_session(stream: TcpStream, store: PcsLiveStore) {
+    let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_else(|_| "unknown".to_string());
+    let mut writer = match stream.try_clone() {
+        Ok(w) => w,
+        Err(e) => {
+            warn!("PCS control server: failed to clone stream for {}: {}", peer, e);
+            return;
+        }
+    };
+    let reader = BufReader::new(stream);
+    info!("PCS control server: session started with {}", peer);
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("PCS control server: read error from {}: {}", peer, e);
+                return;
+            }
+        };
+        let response = handle_command(&store, line.trim());
+        if writer.write_all(format!("{}\n", response).as_bytes()).is_err() {
+            warn!("PCS control server: failed to write response to {}", peer);
+            return;
+        }
+    }
+    info!("PCS control server: session with {} closed", peer);
+}
+
+fn handle_command(store: &PcsLiveStore, line: &str) -> String {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    match parts.as_slice() {
+        ["set", logical_id, field, value] => {
+            let logical_id = match parse_logical_id(logical_id) {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            let value: f32 = match value.parse() {
+                Ok(v) => v,
+                Err(_) => return format!("ERR invalid value '{}'", value),
+            };
+            match store.set_field(logical_id, field, value) {
+                Ok(()) => "OK".to_string(),
+                Err(e) => format!("ERR {}", e),
+            }
+        }
+        ["inject_fault", logical_id, fault_name] => {
+            let logical_id = match parse_logical_id(logical_id) {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            store.inject_fault(logical_id, fault_name);
+            "OK".to_string()
+        }
+        ["get", logical_id] => {
+            let logical_id = match parse_logical_id(logical_id) {
+                Ok(id) => id,
+                Err(e) => return e,
+            };
+            match store.get_known(logical_id) {
+                Some(values) => format!(
+                    "OK soc={} status={} max_charging_power={} max_discharging_power={} max_capacitive_power={} max_inductive_power={} fault={}",
+                    values.soc,
+                    values.status,
+                    values.max_charging_power,
+                    values.max_discharging_power,
+                    values.max_capacitive_power,
+                    values.max_inductive_power,
+                    store.active_fault(logical_id).unwrap_or_else(|| "none".to_string()),
+                ),
+                None => format!("ERR unknown logical_id {} (no values set yet)", logical_id),
+            }
+        }
+        [] => "ERR empty command".to_string(),
+        _ => format!("ERR unrecognized command '{}'", line),
+    }
+}
+
+fn parse_logical_id(s: &str) -> Result<u16, String> {
+    s.parse::<u16>().map_err(|_| format!("ERR invalid logical_id '{}'", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_updates_value_and_reports_ok() {
+        let store = PcsLiveStore::new();
+        assert_eq!(handle_command(&store, "set 1 soc 42.5"), "OK");
+        assert_eq!(store.values_or_default(1).soc, 42.5);
+    }
+
+    #[test]
+    fn test_set_rejects_unknown_field() {
+        let store = PcsLiveStore::new();
+        let response = handle_command(&store, "set 1 bogus 1.0");
+        assert!(response.starts_with("ERR"));
+    }
+
+    #[test]
+    fn test_set_rejects_non_numeric_logical_id() {
+        let store = PcsLiveStore::new();
+        let response = handle_command(&store, "set abc soc 1.0");
+        assert!(response.starts_with("ERR"));
+    }
+
+    #[test]
+    fn test_inject_fault_reports_ok_and_is_visible_in_get() {
+        let store = PcsLiveStore::new();
+        assert_eq!(handle_command(&store, "inject_fault 2 overvoltage"), "OK");
+        let response = handle_command(&store, "get 2");
+        assert!(response.contains("fault=overvoltage"));
+    }
+
+    #[test]
+    fn test_get_unknown_logical_id_is_an_error() {
+        let store = PcsLiveStore::new();
+        let response = handle_command(&store, "get 99");
+        assert!(response.starts_with("ERR"));
+    }
+
+    #[test]
+    fn test_get_known_logical_id_dumps_values() {
+        let store = PcsLiveStore::new();
+        store.set_field(5, "soc", 75.0).unwrap();
+        let response = handle_command(&store, "get 5");
+        assert!(response.starts_with("OK"));
+        assert!(response.contains("soc=75"));
+    }
+
+    #[test]
+    fn test_unrecognized_command_is_an_error() {
+        let store = PcsLiveStore::new();
+        let response = handle_command(&store, "frobnicate 1 2 3");
+        assert!(response.starts_with("ERR"));
+    }
+}
@@ -1,8 +1,12 @@
 
+pub mod config_watch;
+pub mod control_server;
 pub mod pms_command_rx;
 pub mod pcs_publisher;
 pub mod retransmit;
 pub mod retransmit_signal;
 
+pub use config_watch::{spawn_config_toml_watch_thread, spawn_nameplate_watch_thread};
+pub use control_server::spawn_pcs_control_server;
 pub use retransmit::spawn_retransmit_thread;
 pub use retransmit_signal::RetransmitSignal;
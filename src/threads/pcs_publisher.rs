@@ -1,22 +1,26 @@
+use crate::goose::buffer_pool::PooledBuffer;
 use crossbeam_channel::Receiver;
 use log::{error, info, warn};
 use pnet_datalink::DataLinkSender;
 use std::thread::{self, JoinHandle};
 
 /// Spawns the GOOSE sender thread that owns both LAN1 and LAN2 transmitters
-/// 
+///
 /// This thread receives encoded GOOSE frames via a channel and sends them
 /// via both LAN1 and LAN2 for redundancy. If one LAN fails, the other continues.
-/// 
+/// Frames arrive as a `PooledBuffer` leased from the retransmit thread's
+/// `BufferPool`; it returns itself to the pool on drop once both sends are
+/// done, so no per-frame allocation happens on this hot path either.
+///
 /// # Arguments
-/// * `goose_rx` - Receiver for encoded GOOSE frames
+/// * `goose_rx` - Receiver for encoded GOOSE frames (pooled buffers)
 /// * `tx_lan1` - Optional LAN1 transmitter
 /// * `tx_lan2` - Optional LAN2 transmitter
-/// 
+///
 /// # Returns
 /// * `Option<JoinHandle<()>>` - Join handle if at least one transmitter is available
 pub fn spawn_pcs_goose_publisher_thread(
-    goose_rx: Receiver<Vec<u8>>,
+    goose_rx: Receiver<PooledBuffer>,
     mut tx_lan1: Option<Box<dyn DataLinkSender>>,
     mut tx_lan2: Option<Box<dyn DataLinkSender>>,
 ) -> Option<JoinHandle<()>> {
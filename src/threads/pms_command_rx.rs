@@ -1,14 +1,20 @@
 use crate::goose::packet_processor::PacketData;
+use crate::goose::pdu::Iec61850Time;
+use crate::network::ptp_clock::{PtpClock, MAX_GOOSE_TIMESTAMP_SKEW};
 use crate::os::linux_rt::pin_thread_to_core;
 use crate::pcs;
-use crate::pms::types::PmsGooseCmdSubscriber;
-use crate::pms::types::PmsConfig;
-use crossbeam_channel::Receiver;
+use crate::pms::types::{apply_pms_command, apply_pms_failsafe, PcsCommandTable, PmsConfig, PmsGooseCmdSubscriber};
+use crossbeam_channel::{select, Receiver};
 use dashmap::DashMap;
 use libc::sched_getcpu;
 use log::{error, info, warn};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How often each worker polls `pms_subscribers` for `invalidity_time` timeouts
+/// while otherwise blocked waiting on `lan1_rx`/`lan2_rx`.
+const INVALIDITY_SWEEP_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Spawns worker threads for processing GOOSE packets
 ///
@@ -19,19 +25,28 @@ use std::thread::{self, JoinHandle};
 /// - Updates PCS commands based on received frames
 ///
 /// # Arguments
-/// * `packet_rx` - Receiver for incoming packets (LAN ID, PacketData)
+/// * `lan1_rx` / `lan2_rx` - Distinct per-LAN packet receivers. A worker services
+///   whichever has data first via `select!`, giving true hot-standby redundancy on
+///   ingress instead of treating both LANs as one undifferentiated stream
 /// * `pms_config` - Shared PMS configuration (Arc for thread-safe sharing)
 /// * `pms_subscribers` - Shared PMS GOOSE command subscribers (DashMap provides internal concurrency)
+/// * `pcs_commands` - Command values fanned out to each PCS `logical_id` on an accepted state change
 /// * `num_workers` - Number of worker threads to spawn
+/// * `ptp_clock` - PTP-disciplined clock to stamp liveness timestamps and validate
+///   received frames' `t` field with; `None` falls back to undisciplined `SystemTime`
+///   and skips timestamp plausibility checks entirely
 ///
 /// # Returns
 /// * `Vec<JoinHandle<()>>` - Vector of join handles for spawned threads
 pub fn spawn_worker_threads(
-    packet_rx: Receiver<(u16, PacketData)>,
+    lan1_rx: Receiver<PacketData>,
+    lan2_rx: Receiver<PacketData>,
     pms_config: Arc<PmsConfig>,
     pms_subscribers: Arc<DashMap<u16, PmsGooseCmdSubscriber>>,
     pcs_goose_publishers: Arc<DashMap<u16, pcs::types::PcsGoosePublisher>>,
+    pcs_commands: Arc<PcsCommandTable>,
     num_workers: usize,
+    ptp_clock: Option<PtpClock>,
 ) -> Vec<JoinHandle<()>> {
     let mut handles = Vec::new();
 
@@ -44,9 +59,12 @@ pub fn spawn_worker_threads(
     );
 
     for core_id in 1..=worker_count {
-        let rx = packet_rx.clone();
+        let lan1_rx = lan1_rx.clone();
+        let lan2_rx = lan2_rx.clone();
         let pms_config = Arc::clone(&pms_config);
         let pms_subscribers = Arc::clone(&pms_subscribers);
+        let pcs_commands = Arc::clone(&pcs_commands);
+        let ptp_clock = ptp_clock.clone();
 
         let handle = thread::spawn(move || {
             // Pin thread to core
@@ -56,134 +74,40 @@ pub fn spawn_worker_threads(
                 info!("Worker pinned to CPU: {}", unsafe { sched_getcpu() });
             }
 
-            // Process packets from the channel
-            while let Ok((lan_id, packet_data)) = rx.recv() {
-                // REDUCED LOGGING: Too frequent, causes I/O contention
-                // log::debug!("Worker on core {} received packet from LAN{}", core_id, lan_id);
-                
-                // Decode GOOSE frame
-                let mut rx_header = Default::default();
-                let mut rx_pdu = Default::default();
-
-                if crate::goose::pdu::decodeGooseFrame(
-                    &mut rx_header,
-                    &mut rx_pdu,
-                    &packet_data.data,
-                    0,
-                )
-                .is_ok()
-                {
-                    // Check if the APPID is included in the pms_command appid list
-                    let appid = u16::from_be_bytes(rx_header.APPID);
-                    if !pms_config.pms_command_appid_list.contains(&appid) {
-                        warn!(
-                            "Received GOOSE frame with unknown APPID 0x{:04X} from LAN{}",
-                            appid, lan_id
-                        );
-                        continue;
-                    }
-
-                    // Find the corresponding pms command subscriber for this appid
-                    if let Some(mut pms_entry) = pms_subscribers.get_mut(&appid) {
-                        // IEC 61850-8-1 GOOSE freshness validation with restart detection:
-                        // 
-                        // A frame is newer if:
-                        //   1. stNum (state number) is greater, OR
-                        //   2. stNum is equal AND sqNum (sequence number) is greater, OR
-                        //   3. Sender restart detected (stNum dropped significantly, suggesting reset to 0)
-                        //
-                        // Restart detection: If rx_stNum < current_stNum by large margin (e.g., > 100),
-                        // assume sender restarted and accept the new frame.
-                        let current_stnum = pms_entry.goosepdu.stNum;
-                        let current_sqnum = pms_entry.goosepdu.sqNum;
-                        let current_confrev = pms_entry.goosepdu.confRev;
-                        let rx_stnum = rx_pdu.stNum;
-                        let rx_sqnum = rx_pdu.sqNum;
-                        let rx_confrev = rx_pdu.confRev;
-
-                        // Detect sender restart: stNum went backwards significantly
-                        const RESTART_THRESHOLD: u32 = 100;
-                        let is_restart = current_stnum > RESTART_THRESHOLD && 
-                                        rx_stnum < current_stnum && 
-                                        (current_stnum - rx_stnum) > RESTART_THRESHOLD;
-
-                        // Configuration revision changed also indicates restart/reconfiguration
-                        let is_reconfig = rx_confrev != current_confrev;
-
-                        let is_newer = (rx_stnum > current_stnum) || 
-                                       (rx_stnum == current_stnum && rx_sqnum > current_sqnum) ||
-                                       is_restart ||
-                                       is_reconfig;
-
-                        if !is_newer {
-                            // Old or duplicate frame - ignore to prevent overwriting newer data
-                            // REDUCED LOGGING: Too frequent
-                            // log::trace!(
-                            //     "Ignoring stale GOOSE frame APPID 0x{:04X} LAN{}: rx(st:{},sq:{}) <= current(st:{},sq:{})",
-                            //     appid, lan_id, rx_stnum, rx_sqnum, current_stnum, current_sqnum
-                            // );
-                            continue;
-                        }
-
-                        // Frame is newer - update stored PDU
-                        if is_restart {
-                            info!(
-                                "GOOSE sender RESTART detected APPID 0x{:04X} LAN{}: stNum dropped {} → {} (confRev:{} → {})",
-                                appid, lan_id, current_stnum, rx_stnum, current_confrev, rx_confrev
-                            );
-                        } else if is_reconfig {
-                            info!(
-                                "GOOSE RECONFIGURATION detected APPID 0x{:04X} LAN{}: confRev changed {} → {} (stNum:{} → {})",
-                                appid, lan_id, current_confrev, rx_confrev, current_stnum, rx_stnum
-                            );
-                        } else {
-                            info!(
-                                "Received new GOOSE command APPID 0x{:04X} LAN{}: (st:{},sq:{}) > (st:{},sq:{})",
-                                appid, lan_id, rx_stnum, rx_sqnum, current_stnum, current_sqnum
-                            );
-                        }
-                        
-                        pms_entry.goosepdu = rx_pdu.clone();
-                        pms_entry.last_update_time = Some(std::time::SystemTime::now());
-
-                        // Get the list of PCS that should receive this command
-                        if let Some(pcs_list) = pms_config.pms_command_pcs_mapping.get(&appid) {
-                            // Process command data for each PCS in the list
-                            // TODO: Parse allData and update PCS command values
-                            info!(
-                                "Command for APPID 0x{:04X} affects {} PCS units: {:?}",
-                                appid, pcs_list.len(), pcs_list
-                            );
-                            
-                            // Extract command data from GOOSE allData
-                            // The allData structure should contain:
-                            // - Boolean enable flags (active/reactive power control)
-                            // - Float setpoint values (active/reactive power)
-                            // This needs to be implemented based on the actual GOOSE data structure
-
-                            rx_pdu.allData.iter().for_each(|data| {
-                                // Placeholder: Log data types received
-                                info!(
-                                    "Received GOOSE allData item of type {:?} for APPID 0x{:04X}",
-                                    data., appid
-                                );
-                                // Actual parsing and PCS command updates go here
-                            });         
-                            
-                        } else {
-                            warn!(
-                                "No PCS mapping found for APPID 0x{:04X} from LAN{}",
-                                appid, lan_id
-                            );
-                        }
-                    } else {
-                        warn!(
-                            "No PMS command subscriber found for APPID 0x{:04X} from LAN{}",
-                            appid, lan_id
-                        );
+            // Service whichever LAN has data first. A LAN whose channel has
+            // disconnected is dropped from the select so a failed/removed
+            // capture thread on one LAN doesn't stall processing on the
+            // other; the default arm still sweeps pms_subscribers for
+            // invalidity_time timeouts even while neither LAN has data.
+            let mut lan1_alive = true;
+            let mut lan2_alive = true;
+            while lan1_alive || lan2_alive {
+                select! {
+                    recv(lan1_rx) -> msg if lan1_alive => match msg {
+                        Ok(packet_data) => handle_packet(
+                            1,
+                            &packet_data,
+                            &pms_config,
+                            &pms_subscribers,
+                            &pcs_commands,
+                            ptp_clock.as_ref(),
+                        ),
+                        Err(_) => lan1_alive = false,
+                    },
+                    recv(lan2_rx) -> msg if lan2_alive => match msg {
+                        Ok(packet_data) => handle_packet(
+                            2,
+                            &packet_data,
+                            &pms_config,
+                            &pms_subscribers,
+                            &pcs_commands,
+                            ptp_clock.as_ref(),
+                        ),
+                        Err(_) => lan2_alive = false,
+                    },
+                    default(INVALIDITY_SWEEP_INTERVAL) => {
+                        sweep_invalidity(&pms_subscribers, &pms_config, &pcs_commands);
                     }
-                } else {
-                    warn!("Failed to decode GOOSE frame from LAN{}", lan_id);
                 }
             }
 
@@ -194,3 +118,100 @@ pub fn spawn_worker_threads(
 
     handles
 }
+
+/// Decode one packet and, if its APPID is a known PMS command subscriber,
+/// apply IEC 61850 GOOSE freshness rules and fan out an accepted state change
+/// to the mapped PCS units. When `ptp_clock` is supplied, the frame's `t`
+/// field is first checked for plausibility against PTP time and liveness
+/// timestamps are stamped from the clock rather than undisciplined
+/// `SystemTime`.
+fn handle_packet(
+    lan_id: u16,
+    packet_data: &PacketData,
+    pms_config: &PmsConfig,
+    pms_subscribers: &DashMap<u16, PmsGooseCmdSubscriber>,
+    pcs_commands: &PcsCommandTable,
+    ptp_clock: Option<&PtpClock>,
+) {
+    let mut rx_header = Default::default();
+    let mut rx_pdu = Default::default();
+
+    if crate::goose::pdu::decodeGooseFrame(&mut rx_header, &mut rx_pdu, &packet_data.data, 0).is_err() {
+        warn!("Failed to decode GOOSE frame from LAN{}", lan_id);
+        return;
+    }
+
+    let appid = u16::from_be_bytes(rx_header.APPID);
+    if !pms_config.pms_command_appid_list.contains(&appid) {
+        warn!(
+            "Received GOOSE frame with unknown APPID 0x{:04X} from LAN{}",
+            appid, lan_id
+        );
+        return;
+    }
+
+    let Some(mut pms_entry) = pms_subscribers.get_mut(&appid) else {
+        warn!(
+            "No PMS command subscriber found for APPID 0x{:04X} from LAN{}",
+            appid, lan_id
+        );
+        return;
+    };
+
+    if let Some(clock) = ptp_clock {
+        let t = Iec61850Time::from_bytes(rx_pdu.t);
+        if !clock.validate_goose_timestamp(t, MAX_GOOSE_TIMESTAMP_SKEW) {
+            warn!(
+                "Rejected PMS GOOSE APPID 0x{:04X} from LAN{}: t field implausible vs PTP time",
+                appid, lan_id
+            );
+            return;
+        }
+    }
+
+    let is_state_change = match ptp_clock {
+        Some(clock) => pms_entry.accept_frame_ptp(lan_id, &rx_pdu, clock),
+        None => pms_entry.accept_frame(lan_id, &rx_pdu),
+    };
+    if !is_state_change {
+        // Either an exact duplicate (ignored by accept_frame, liveness untouched)
+        // or a mere retransmission (liveness already refreshed, no command change).
+        return;
+    }
+
+    info!(
+        "Accepted PMS GOOSE command APPID 0x{:04X} LAN{}: stNum {} sqNum {}",
+        appid, lan_id, rx_pdu.stNum, rx_pdu.sqNum
+    );
+
+    match (
+        pms_config.pms_command_pcs_mapping.get(&appid),
+        pms_config.pms_command_schema.get(&appid),
+    ) {
+        (Some(pcs_list), Some(schema)) => {
+            apply_pms_command(appid, &rx_pdu.allData, pcs_list, schema, pcs_commands)
+        }
+        _ => warn!(
+            "No PCS mapping or command schema found for APPID 0x{:04X} from LAN{}",
+            appid, lan_id
+        ),
+    }
+}
+
+/// Mark any PMS command subscriber whose `invalidity_time` has elapsed without
+/// a fresh frame as invalid, and on that transition apply `pms_config`'s
+/// configured failsafe action to the PCS units that subscription commands.
+fn sweep_invalidity(
+    pms_subscribers: &DashMap<u16, PmsGooseCmdSubscriber>,
+    pms_config: &PmsConfig,
+    pcs_commands: &PcsCommandTable,
+) {
+    for mut entry in pms_subscribers.iter_mut() {
+        let appid = entry.goose_appid;
+        if entry.sweep_invalidity() {
+            if let Some(pcs_list) = pms_config.pms_command_pcs_mapping.get(&appid) {
+                apply_pms_failsafe(appid, pcs_list, pms_config.failsafe_action, pcs_commands);
+            }
+        }
+    }
+}
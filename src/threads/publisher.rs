@@ -1,8 +1,11 @@
-//(ethher_head,goose_pdu) type definition for goose_publisher
+//(ethher_head,goose_pdu,dataset_layout) type definition for goose_publisher
 use anyhow::{Context, Result};
 use log::{error, info};
 
-type PlcPublisherGooseFrame = (EthernetHeader, IECGoosePdu);
+/// The third element is the `DataSetLayout` the frame's `allData` was built
+/// from, carried alongside the PDU so `assign_to_goose_frame` can consume it
+/// without threading `PublisherConfig` through the retransmit/send path.
+type PlcPublisherGooseFrame = (EthernetHeader, IECGoosePdu, DataSetLayout);
 
 // use std::sync::atomic::{AtomicBool, Ordering};
 // use std::sync::{Arc, RwLock};
@@ -11,7 +14,7 @@ type PlcPublisherGooseFrame = (EthernetHeader, IECGoosePdu);
 
 // receiving data from PLC and pubish to GOOSE publisher
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StPlcCmdPub {
     pub pcs_logical_id: u16,
     pub protocol: u8,
@@ -20,7 +23,7 @@ pub struct StPlcCmdPub {
     pub spare: [u8; 16],
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StPlcCmdAll {
     pub protocol: u8,
     pub nanotimer: u64, // timer in nanoseconds since epoch if send timer diff needed to check .
@@ -331,25 +334,697 @@ pub unsafe fn deserialize_stplccmdall_unsafe(data: &[u8]) -> io::Result<StPlcCmd
     })
 }
 
+/// Frame that can write its little-endian wire bytes into a caller-owned
+/// slice, mirroring `deserialize_stplccmdall_unsafe`'s layout so a test or
+/// loopback simulator can produce a datagram without hand-rolling the byte
+/// layout. Implemented by both [`StPlcCmdAll`] (the full datagram) and
+/// [`StPlcCmdPub`] (one 27-byte command record within it).
+pub trait WritableFrame {
+    /// Number of bytes `write_to_slice` writes, so callers can pre-size `buf`.
+    fn len_written(&self) -> usize;
+
+    /// Write this frame's little-endian wire bytes into the front of `buf`.
+    ///
+    /// # Errors
+    /// Returns `Err` if `buf` is shorter than `len_written()`.
+    fn write_to_slice(&self, buf: &mut [u8]) -> io::Result<usize>;
+}
+
+impl WritableFrame for StPlcCmdPub {
+    fn len_written(&self) -> usize {
+        27 // protocol(1) + pcs_logical_id(2) + active_power(4) + reactive_power(4) + spare(16)
+    }
+
+    fn write_to_slice(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let needed = self.len_written();
+        if buf.len() < needed {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                format!(
+                    "buffer too short for StPlcCmdPub: need {} bytes, got {}",
+                    needed,
+                    buf.len()
+                ),
+            ));
+        }
+
+        buf[0] = self.protocol;
+        buf[1..3].copy_from_slice(&self.pcs_logical_id.to_le_bytes());
+        buf[3..7].copy_from_slice(&self.pcs_active_power.to_le_bytes());
+        buf[7..11].copy_from_slice(&self.pcs_reactive_power.to_le_bytes());
+        buf[11..27].copy_from_slice(&self.spare);
+        Ok(needed)
+    }
+}
+
+impl WritableFrame for StPlcCmdAll {
+    fn len_written(&self) -> usize {
+        // protocol(1) + nanotimer(8) + number_of_pcs(2) + spare(16) + pcs_cmds count(2) = 29
+        29 + self.pcs_cmds.len() * 27
+    }
+
+    fn write_to_slice(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let needed = self.len_written();
+        if buf.len() < needed {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                format!(
+                    "buffer too short for StPlcCmdAll: need {} bytes, got {}",
+                    needed,
+                    buf.len()
+                ),
+            ));
+        }
+
+        let mut offset = 0;
+        buf[offset] = self.protocol;
+        offset += 1;
+        buf[offset..offset + 8].copy_from_slice(&self.nanotimer.to_le_bytes());
+        offset += 8;
+        buf[offset..offset + 2].copy_from_slice(&self.number_of_pcs.to_le_bytes());
+        offset += 2;
+        buf[offset..offset + 16].copy_from_slice(&self.spare);
+        offset += 16;
+        buf[offset..offset + 2].copy_from_slice(&(self.pcs_cmds.len() as u16).to_le_bytes());
+        offset += 2;
+
+        for cmd in &self.pcs_cmds {
+            offset += cmd.write_to_slice(&mut buf[offset..])?;
+        }
+
+        Ok(offset)
+    }
+}
+
+impl StPlcCmdAll {
+    /// Serialize into `buf`'s leading bytes using the little-endian layout
+    /// `deserialize_stplccmdall_unsafe` expects (protocol, nanotimer,
+    /// number_of_pcs, spare, pcs_cmds count, then each 27-byte command).
+    /// Thin wrapper over [`WritableFrame::write_to_slice`].
+    ///
+    /// # Errors
+    /// Returns `Err` if `buf` is shorter than `self.len_written()`.
+    pub fn serialize_stplccmdall(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.write_to_slice(buf)
+    }
+
+    /// Allocating convenience wrapper over [`StPlcCmdAll::serialize_stplccmdall`]
+    /// for callers that don't already have a reusable buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; self.len_written()];
+        self.serialize_stplccmdall(&mut buf)
+            .expect("buffer sized via len_written");
+        buf
+    }
+}
+
+/// Protocol byte identifying a [`StPlcCmdAll`] PCS power-command datagram.
+pub const PROTOCOL_CMD_ALL: u8 = 20;
+/// Protocol byte identifying a [`StPlcMgmtCmd`] management/diagnostic request.
+pub const PROTOCOL_MGMT_CMD: u8 = 21;
+/// Protocol byte identifying a [`StPlcMgmtResponse`] reply to a mgmt request.
+pub const PROTOCOL_MGMT_RESPONSE: u8 = 22;
+
+/// One of the operations a [`StPlcMgmtCmd`] can request against the live
+/// `Vec<PlcPublisherGooseFrame>`, keyed by `goID`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlcMgmtOp {
+    /// Reset `stNum`/`sqNum` back to 0, e.g. after a subscriber restart.
+    ResetCounters = 0,
+    /// Flip `goose_pdu.simulation`.
+    ToggleSimulation = 1,
+    /// No mutation; the response carries the current state.
+    QueryState = 2,
+    /// Enable or disable transmission for this publisher, per `StPlcMgmtCmd::enabled`.
+    SetEnabled = 3,
+}
+
+impl PlcMgmtOp {
+    fn from_u8(v: u8) -> io::Result<Self> {
+        match v {
+            0 => Ok(PlcMgmtOp::ResetCounters),
+            1 => Ok(PlcMgmtOp::ToggleSimulation),
+            2 => Ok(PlcMgmtOp::QueryState),
+            3 => Ok(PlcMgmtOp::SetEnabled),
+            other => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unknown mgmt op code: {}", other),
+            )),
+        }
+    }
+}
+
+/// Maximum `goID` length the mgmt wire format can carry; matches the kind of
+/// datSet/goID names `load_plc_publisher_config` validates elsewhere, with
+/// headroom.
+const MGMT_GOOSE_ID_MAX_LEN: usize = 64;
+
+/// Out-of-band management/diagnostic request, multiplexed onto the same UDP
+/// socket as [`StPlcCmdAll`] via the `protocol` byte
+/// ([`PROTOCOL_MGMT_CMD`] vs [`PROTOCOL_CMD_ALL`]). Lets an operator reset a
+/// publisher's retransmission counters, toggle its `simulation` bit, query
+/// its live state, or enable/disable it without a separate control-plane
+/// socket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StPlcMgmtCmd {
+    pub protocol: u8,
+    pub op: PlcMgmtOp,
+    pub goose_id: String,
+    /// Only meaningful for `PlcMgmtOp::SetEnabled`; ignored otherwise.
+    pub enabled: bool,
+}
+
+impl StPlcMgmtCmd {
+    /// Build a request, defaulting `protocol` to [`PROTOCOL_MGMT_CMD`].
+    pub fn new(op: PlcMgmtOp, goose_id: impl Into<String>, enabled: bool) -> Self {
+        StPlcMgmtCmd {
+            protocol: PROTOCOL_MGMT_CMD,
+            op,
+            goose_id: goose_id.into(),
+            enabled,
+        }
+    }
+
+    /// Parse a [`PROTOCOL_MGMT_CMD`] datagram: protocol(1) + op(1) +
+    /// enabled(1) + goID length(1) + goID bytes.
+    fn deserialize(data: &[u8]) -> io::Result<Self> {
+        const HEADER_SIZE: usize = 1 + 1 + 1 + 1;
+        if data.len() < HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Mgmt cmd too short: {} bytes, need at least {}",
+                    data.len(),
+                    HEADER_SIZE
+                ),
+            ));
+        }
+
+        let protocol = data[0];
+        if protocol != PROTOCOL_MGMT_CMD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid protocol: expected {}, got {}", PROTOCOL_MGMT_CMD, protocol),
+            ));
+        }
+        let op = PlcMgmtOp::from_u8(data[1])?;
+        let enabled = data[2] != 0;
+        let goose_id_len = data[3] as usize;
+
+        if data.len() < HEADER_SIZE + goose_id_len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Mgmt cmd too short: {} bytes, expected {} for a {}-byte goID",
+                    data.len(),
+                    HEADER_SIZE + goose_id_len,
+                    goose_id_len
+                ),
+            ));
+        }
+        let goose_id = String::from_utf8(data[HEADER_SIZE..HEADER_SIZE + goose_id_len].to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("goID is not valid UTF-8: {}", e)))?;
+
+        Ok(StPlcMgmtCmd {
+            protocol,
+            op,
+            goose_id,
+            enabled,
+        })
+    }
+
+    /// Apply this request to `frames`, mutating the matching live frame in
+    /// place (counters/`simulation`) or flipping `enabled` in `runtime`, and
+    /// return the response datagram to send back to the operator.
+    ///
+    /// A `goID` with no matching frame yields an error response rather than
+    /// `Err`, since "no such publisher" is a normal, expected answer over
+    /// this channel, not a malformed-request condition.
+    pub fn apply(
+        &self,
+        frames: &mut [PlcPublisherGooseFrame],
+        runtime: &mut PlcMgmtRuntimeState,
+    ) -> StPlcMgmtResponse {
+        let Some((_, pdu, _)) = frames.iter_mut().find(|(_, pdu, _)| pdu.goID == self.goose_id) else {
+            return StPlcMgmtResponse::error(&self.goose_id, "no publisher with that goID");
+        };
+
+        match self.op {
+            PlcMgmtOp::ResetCounters => {
+                pdu.stNum = 0;
+                pdu.sqNum = 0;
+                info!("Mgmt: reset stNum/sqNum for goID '{}'", self.goose_id);
+            }
+            PlcMgmtOp::ToggleSimulation => {
+                pdu.simulation = !pdu.simulation;
+                info!(
+                    "Mgmt: toggled simulation to {} for goID '{}'",
+                    pdu.simulation, self.goose_id
+                );
+            }
+            PlcMgmtOp::SetEnabled => {
+                runtime.set_enabled(&self.goose_id, self.enabled);
+                info!(
+                    "Mgmt: set enabled={} for goID '{}'",
+                    self.enabled, self.goose_id
+                );
+            }
+            PlcMgmtOp::QueryState => {}
+        }
+
+        StPlcMgmtResponse::ok(pdu, runtime.is_enabled(&self.goose_id))
+    }
+
+    /// Allocating convenience wrapper over [`WritableFrame::write_to_slice`]
+    /// for callers that don't already have a reusable buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut buf = vec![0u8; self.len_written()];
+        self.write_to_slice(&mut buf)
+            .expect("buffer sized via len_written");
+        buf
+    }
+}
+
+impl WritableFrame for StPlcMgmtCmd {
+    fn len_written(&self) -> usize {
+        4 + self.goose_id.len().min(MGMT_GOOSE_ID_MAX_LEN)
+    }
+
+    fn write_to_slice(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let needed = self.len_written();
+        if buf.len() < needed {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                format!(
+                    "buffer too short for StPlcMgmtCmd: need {} bytes, got {}",
+                    needed,
+                    buf.len()
+                ),
+            ));
+        }
+        let goose_id = &self.goose_id.as_bytes()[..needed - 4];
+        buf[0] = self.protocol;
+        buf[1] = self.op as u8;
+        buf[2] = self.enabled as u8;
+        buf[3] = goose_id.len() as u8;
+        buf[4..needed].copy_from_slice(goose_id);
+        Ok(needed)
+    }
+}
+
+/// Reply to a [`StPlcMgmtCmd`], carrying the publisher's state after the
+/// request was applied (or, on `status != 0`, just an error).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StPlcMgmtResponse {
+    pub protocol: u8,
+    pub status: u8,
+    pub goose_id: String,
+    pub st_num: u32,
+    pub sq_num: u32,
+    pub conf_rev: u32,
+    pub simulation: bool,
+    pub enabled: bool,
+}
+
+impl StPlcMgmtResponse {
+    fn ok(pdu: &IECGoosePdu, enabled: bool) -> Self {
+        StPlcMgmtResponse {
+            protocol: PROTOCOL_MGMT_RESPONSE,
+            status: 0,
+            goose_id: pdu.goID.clone(),
+            st_num: pdu.stNum,
+            sq_num: pdu.sqNum,
+            conf_rev: pdu.confRev,
+            simulation: pdu.simulation,
+            enabled,
+        }
+    }
+
+    fn error(goose_id: &str, reason: &str) -> Self {
+        error!("Mgmt: request for goID '{}' failed: {}", goose_id, reason);
+        StPlcMgmtResponse {
+            protocol: PROTOCOL_MGMT_RESPONSE,
+            status: 1,
+            goose_id: goose_id.to_string(),
+            st_num: 0,
+            sq_num: 0,
+            conf_rev: 0,
+            simulation: false,
+            enabled: false,
+        }
+    }
+}
+
+impl WritableFrame for StPlcMgmtResponse {
+    fn len_written(&self) -> usize {
+        // protocol(1) + status(1) + stNum(4) + sqNum(4) + confRev(4) + simulation(1)
+        // + enabled(1) + goID length(1) + goID bytes
+        17 + self.goose_id.len().min(MGMT_GOOSE_ID_MAX_LEN)
+    }
+
+    fn write_to_slice(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let needed = self.len_written();
+        if buf.len() < needed {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                format!(
+                    "buffer too short for StPlcMgmtResponse: need {} bytes, got {}",
+                    needed,
+                    buf.len()
+                ),
+            ));
+        }
+        let goose_id = &self.goose_id.as_bytes()[..needed - 17];
+        let mut offset = 0;
+        buf[offset] = self.protocol;
+        offset += 1;
+        buf[offset] = self.status;
+        offset += 1;
+        buf[offset..offset + 4].copy_from_slice(&self.st_num.to_le_bytes());
+        offset += 4;
+        buf[offset..offset + 4].copy_from_slice(&self.sq_num.to_le_bytes());
+        offset += 4;
+        buf[offset..offset + 4].copy_from_slice(&self.conf_rev.to_le_bytes());
+        offset += 4;
+        buf[offset] = self.simulation as u8;
+        offset += 1;
+        buf[offset] = self.enabled as u8;
+        offset += 1;
+        buf[offset] = goose_id.len() as u8;
+        offset += 1;
+        buf[offset..offset + goose_id.len()].copy_from_slice(goose_id);
+        offset += goose_id.len();
+        Ok(offset)
+    }
+}
+
+/// Per-publisher state the mgmt channel can toggle that isn't part of the
+/// IEC 61850 GOOSE PDU shape (so it doesn't belong on `IECGoosePdu` itself):
+/// whether a publisher is currently enabled for transmission. Threaded
+/// alongside the live `Vec<PlcPublisherGooseFrame>` the same way
+/// `PublisherConfigStore` is threaded alongside it for config edits.
+#[derive(Debug, Default, Clone)]
+pub struct PlcMgmtRuntimeState {
+    disabled: std::collections::HashSet<String>,
+}
+
+impl PlcMgmtRuntimeState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self, goose_id: &str) -> bool {
+        !self.disabled.contains(goose_id)
+    }
+
+    pub fn set_enabled(&mut self, goose_id: &str, enabled: bool) {
+        if enabled {
+            self.disabled.remove(goose_id);
+        } else {
+            self.disabled.insert(goose_id.to_string());
+        }
+    }
+}
+
+/// A UDP datagram dispatched by its leading `protocol` byte: either a PCS
+/// power-command datagram or an out-of-band management/diagnostic request.
+/// Lets `StPlcCmdAll` and `StPlcMgmtCmd` share one socket instead of the
+/// management channel needing its own port.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlcDatagram {
+    Commands(StPlcCmdAll),
+    Mgmt(StPlcMgmtCmd),
+}
+
+/// Inspect `data`'s `protocol` byte and dispatch to [`deserialize_stplccmdall`]
+/// or [`StPlcMgmtCmd::deserialize`] accordingly.
+pub fn parse_udp_datagram(data: &[u8]) -> Result<PlcDatagram> {
+    match data.first() {
+        Some(&PROTOCOL_CMD_ALL) => Ok(PlcDatagram::Commands(
+            deserialize_stplccmdall(data).context("Failed to parse StPlcCmdAll datagram")?,
+        )),
+        Some(&PROTOCOL_MGMT_CMD) => Ok(PlcDatagram::Mgmt(
+            StPlcMgmtCmd::deserialize(data).context("Failed to parse StPlcMgmtCmd datagram")?,
+        )),
+        Some(other) => anyhow::bail!("Unrecognized protocol byte: {}", other),
+        None => anyhow::bail!("Empty UDP datagram"),
+    }
+}
+
 // read goose_publisher_cfg.json to get pcs publisher config
 // return vector of struct PublisherConfig
 use serde::{Deserialize, Serialize};
 
+use std::collections::HashMap;
+use std::fmt;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+/// Ethernet MAC address, stored as 6 bytes in network order.
+///
+/// `FromStr` accepts the same three forms the old free-function `parse_mac`
+/// did - colon (`01:0C:CD:01:00:01`), dash (`01-0C-CD-01-00-01`), and bare
+/// hex (`010CCD010001`) - and `Display` always renders the canonical colon
+/// form. `Serialize`/`Deserialize` go through that same string, so a
+/// `PublisherConfigRaw` field typed as `MacAddr` parses and validates once at
+/// deserialization instead of being re-parsed from a `String` on every
+/// `to_runtime()`/`build_publisher_goose_frame` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    /// IEC 61850-8-1 reserves `01-0C-CD-01-xx-xx` for GOOSE multicast
+    /// destination addresses (`01-0C-CD-02-xx-xx` is GSSE, `01-0C-CD-04-xx-xx`
+    /// is multicast SV). A `dst_addr` outside that range still works on the
+    /// wire but won't reach any IEC 61850 GOOSE subscriber listening on the
+    /// conventional range.
+    pub fn is_goose_multicast(&self) -> bool {
+        self.0[0] == 0x01 && self.0[1] == 0x0C && self.0[2] == 0xCD && self.0[3] == 0x01
+    }
+
+    /// Check this address against the GOOSE multicast range: `strict = false`
+    /// logs a warning and returns `Ok` (the current default, since plenty of
+    /// lab/test setups use addresses outside the range on purpose);
+    /// `strict = true` turns the same condition into an `Err`.
+    pub fn check_goose_multicast(&self, strict: bool) -> Result<()> {
+        if self.is_goose_multicast() {
+            return Ok(());
+        }
+        let msg = format!(
+            "{} is outside the IEC 61850 GOOSE multicast range 01:0C:CD:01:xx:xx",
+            self
+        );
+        if strict {
+            anyhow::bail!(msg);
+        }
+        log::warn!("{}", msg);
+        Ok(())
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // try split by common separators first
+        let parts: Vec<&str> = s.split(|c| c == ':' || c == '-' || c == '.').collect();
+        if parts.len() == 6 {
+            let mut mac = [0u8; 6];
+            for (i, p) in parts.iter().enumerate() {
+                if p.len() != 2 {
+                    anyhow::bail!("MAC part '{}' has wrong length", p);
+                }
+                mac[i] = u8::from_str_radix(p, 16)
+                    .with_context(|| format!("invalid hex in '{}'", p))?;
+            }
+            return Ok(MacAddr(mac));
+        }
+
+        // otherwise strip everything except hex digits and try parse as 12 hex chars
+        let s_hex: String = s.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+        if s_hex.len() == 12 {
+            let mut mac = [0u8; 6];
+            for i in 0..6 {
+                let byte = &s_hex[2 * i..2 * i + 2];
+                mac[i] = u8::from_str_radix(byte, 16)
+                    .with_context(|| format!("invalid hex in '{}'", byte))?;
+            }
+            return Ok(MacAddr(mac));
+        }
+
+        anyhow::bail!("invalid MAC format: {}", s)
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
+        )
+    }
+}
+
+impl Serialize for MacAddr {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for MacAddr {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        MacAddr::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// One kind of `allData` entry a `DataSetLayout` can place, matching the
+/// `IECData` variants `assign_to_goose_frame` knows how to fill in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataSetEntryKind {
+    Boolean,
+    Float32,
+}
+
+/// Data-driven description of one PCS's slice of `allData`, replacing the
+/// hardcoded "2 booleans (P active, Q active) then 2 floats (P, Q) per PCS"
+/// `assign_to_goose_frame` used to assume.
+///
+/// `entries` is the ordered kind of every `allData` item one PCS contributes
+/// (e.g. `[Boolean, Boolean, Float32, Float32]` for the original shape, but
+/// any count/order of booleans and floats, interleaved or not, is valid).
+/// `command_codes` maps a `StPlcCmdPub::protocol` command code to which of
+/// that PCS's boolean entries it asserts `true` - indices count only the
+/// `Boolean` entries within `entries`, in order, so inserting a float
+/// doesn't renumber the booleans around it. A code absent from the table
+/// (or every index not listed for a present code) leaves that boolean
+/// `false`. Float entries aren't driven by `command_codes`; they take
+/// `StPlcCmdPub::pcs_active_power`/`pcs_reactive_power` in the order the
+/// float entries appear, cycling if there are more float entries than the
+/// two values a command provides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetLayout {
+    pub entries: Vec<DataSetEntryKind>,
+    pub command_codes: HashMap<u8, Vec<usize>>,
+}
+
+impl DataSetLayout {
+    /// The layout `assign_to_goose_frame` hardcoded before this was
+    /// data-driven: P-active/Q-active booleans then P/Q floats, with command
+    /// codes 10/20/30 asserting P, Q, and both respectively.
+    pub fn legacy() -> Self {
+        let mut command_codes = HashMap::new();
+        command_codes.insert(10, vec![0]);
+        command_codes.insert(20, vec![1]);
+        command_codes.insert(30, vec![0, 1]);
+        DataSetLayout {
+            entries: vec![
+                DataSetEntryKind::Boolean,
+                DataSetEntryKind::Boolean,
+                DataSetEntryKind::Float32,
+                DataSetEntryKind::Float32,
+            ],
+            command_codes,
+        }
+    }
+
+    fn boolean_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|k| **k == DataSetEntryKind::Boolean)
+            .count()
+    }
+
+    /// Reject a layout with no entries, or a `command_codes` index out of
+    /// range of `entries`' boolean count, before it's used to build a frame.
+    fn validate(&self) -> Result<()> {
+        if self.entries.is_empty() {
+            anyhow::bail!("dataSetLayout must have at least one entry");
+        }
+        let boolean_count = self.boolean_count();
+        for (code, indices) in &self.command_codes {
+            for idx in indices {
+                if *idx >= boolean_count {
+                    anyhow::bail!(
+                        "dataSetLayout: command code {} references boolean index {}, but the layout only has {} boolean entries",
+                        code,
+                        idx,
+                        boolean_count
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wire-format companion to `DataSetLayout`: `serde_json` object keys must be
+/// strings, so `command_codes` round-trips through a `String`-keyed map and
+/// `to_runtime`/`to_raw` parse/format the command code the same way other
+/// `PublisherConfigRaw` fields parse their hex/decimal strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataSetLayoutRaw {
+    pub entries: Vec<DataSetEntryKind>,
+    #[serde(rename = "commandCodes")]
+    pub command_codes: HashMap<String, Vec<usize>>,
+}
+
+impl DataSetLayoutRaw {
+    fn to_runtime(&self) -> Result<DataSetLayout> {
+        let mut command_codes = HashMap::with_capacity(self.command_codes.len());
+        for (code, indices) in &self.command_codes {
+            let code: u8 = code
+                .parse()
+                .with_context(|| format!("dataSetLayout: invalid command code '{}'", code))?;
+            command_codes.insert(code, indices.clone());
+        }
+        Ok(DataSetLayout {
+            entries: self.entries.clone(),
+            command_codes,
+        })
+    }
+}
+
+impl DataSetLayout {
+    fn to_raw(&self) -> DataSetLayoutRaw {
+        DataSetLayoutRaw {
+            entries: self.entries.clone(),
+            command_codes: self
+                .command_codes
+                .iter()
+                .map(|(code, indices)| (code.to_string(), indices.clone()))
+                .collect(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublisherConfigRaw {
     #[serde(rename = "srcAddr")]
-    pub src_addr: String,
+    pub src_addr: MacAddr,
     #[serde(rename = "dstAddr")]
-    pub dst_addr: String,
+    pub dst_addr: MacAddr,
     #[serde(rename = "TPID")]
     pub tpid: String,
     #[serde(rename = "TCI")]
     pub tci: String,
-    #[serde(rename = "APPID")]
-    pub appid: String,
+    #[serde(
+        rename = "APPID",
+        deserialize_with = "parse_hex_or_decimal",
+        serialize_with = "serialize_appid_hex"
+    )]
+    pub appid: u16,
     #[serde(rename = "gocbRef")]
     pub gocb_ref: String,
     #[serde(rename = "datSet")]
@@ -364,12 +1039,16 @@ pub struct PublisherConfigRaw {
     pub ndscom: String,
     #[serde(rename = "numberOfPcs")]
     pub number_of_pcs: String,
+    /// Dataset layout for this publisher; absent (or missing from an older
+    /// config file) falls back to `DataSetLayout::legacy()` in `to_runtime`.
+    #[serde(rename = "dataSetLayout", default)]
+    pub layout: Option<DataSetLayoutRaw>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PublisherConfig {
-    pub src_addr: String,
-    pub dst_addr: String,
+    pub src_addr: MacAddr,
+    pub dst_addr: MacAddr,
     pub tpid: u16,
     pub tci: u16,
     pub appid: u16,
@@ -380,19 +1059,19 @@ pub struct PublisherConfig {
     pub conf_rev: u32,
     pub ndscom: bool,
     pub number_of_pcs: u32,
+    pub layout: DataSetLayout,
 }
 
 impl PublisherConfigRaw {
     pub fn to_runtime(&self) -> Result<PublisherConfig> {
         Ok(PublisherConfig {
-            src_addr: self.src_addr.clone(),
-            dst_addr: self.dst_addr.clone(),
+            src_addr: self.src_addr,
+            dst_addr: self.dst_addr,
             tpid: u16::from_str_radix(self.tpid.trim_start_matches("0x"), 16)
                 .context("Failed to parse TPID")?,
             tci: u16::from_str_radix(self.tci.trim_start_matches("0x"), 16)
                 .context("Failed to parse TCI")?,
-            appid: u16::from_str_radix(self.appid.trim_start_matches("0x"), 16)
-                .context("Failed to parse APPID")?,
+            appid: self.appid,
             gocb_ref: self.gocb_ref.clone(),
             dat_set: self.dat_set.clone(),
             goose_id: self.goose_id.clone(),
@@ -406,10 +1085,80 @@ impl PublisherConfigRaw {
                 .number_of_pcs
                 .parse::<u32>()
                 .context("Failed to parse number_of_pcs")?,
+            layout: match &self.layout {
+                Some(raw) => raw.to_runtime()?,
+                None => DataSetLayout::legacy(),
+            },
         })
     }
 }
 
+impl PublisherConfig {
+    /// Inverse of `PublisherConfigRaw::to_runtime`, used by `PublisherConfigStore::persist`
+    /// to write the live, typed config back out in the same hex-string JSON shape it was
+    /// read in.
+    fn to_raw(&self) -> PublisherConfigRaw {
+        PublisherConfigRaw {
+            src_addr: self.src_addr,
+            dst_addr: self.dst_addr,
+            tpid: format!("0x{:04X}", self.tpid),
+            tci: format!("0x{:04X}", self.tci),
+            appid: self.appid,
+            gocb_ref: self.gocb_ref.clone(),
+            dat_set: self.dat_set.clone(),
+            goose_id: self.goose_id.clone(),
+            simulation: self.simulation.to_string(),
+            conf_rev: self.conf_rev.to_string(),
+            ndscom: self.ndscom.to_string(),
+            number_of_pcs: self.number_of_pcs.to_string(),
+            layout: Some(self.layout.to_raw()),
+        }
+    }
+}
+
+/// Parse a `u16` written either as decimal (`"12288"`) or hex (`"0x3000"`) - the
+/// core of `parse_hex_or_decimal`'s `deserialize_with`, factored out so
+/// `apply_config_override` can parse an `appid=...` override the same way
+/// without going through a `serde::Deserializer`.
+fn parse_appid_str(raw: &str) -> Result<u16> {
+    let trimmed = raw.trim();
+    let parsed = match trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        Some(hex) => u16::from_str_radix(hex, 16),
+        None => trimmed.parse::<u16>(),
+    };
+    parsed.with_context(|| format!("invalid APPID '{}'", raw))
+}
+
+/// GOOSE APPIDs are conventionally written in hex in SCL/engineering tools (`"0x3000"`) but
+/// are just as often pasted in decimal (`12288`); accept either as `PublisherConfigRaw::appid`'s
+/// `deserialize_with` so a config file author doesn't have to convert.
+fn parse_hex_or_decimal<'de, D>(deserializer: D) -> std::result::Result<u16, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum HexOrDecimal {
+        Str(String),
+        Num(u64),
+    }
+
+    match HexOrDecimal::deserialize(deserializer)? {
+        HexOrDecimal::Num(n) => u16::try_from(n)
+            .map_err(|_| serde::de::Error::custom(format!("APPID {} out of range for u16", n))),
+        HexOrDecimal::Str(s) => parse_appid_str(&s).map_err(serde::de::Error::custom),
+    }
+}
+
+/// Inverse of `parse_hex_or_decimal`: always write APPID back out as hex, matching the other
+/// hex-string fields (`TPID`, `TCI`) in the on-disk config shape.
+fn serialize_appid_hex<S>(appid: &u16, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&format!("0x{:04X}", appid))
+}
+
 pub fn load_plc_publisher_config(cfg_path: String) -> Result<Vec<PublisherConfig>> {
     info!("Loading publisher config from: {}", cfg_path);
 
@@ -445,6 +1194,68 @@ pub fn load_plc_publisher_config(cfg_path: String) -> Result<Vec<PublisherConfig
     }
     Ok(result)
 }
+
+/// Apply `key=value` overrides (e.g. from the command line) on top of a base
+/// `Vec<PublisherConfig>` loaded by `load_plc_publisher_config`, so test
+/// automation can reuse one base scenario with scalar tweaks instead of
+/// maintaining a full config file per variant.
+///
+/// Each entry in `overrides` is `[<config index>.]<field>=<value>`; the index
+/// prefix is optional and defaults to `0` (the common single-config case).
+/// `<field>` is one of `PublisherConfig`'s field names (`src_addr`, `dst_addr`,
+/// `tpid`, `tci`, `appid`, `gocb_ref`, `dat_set`, `goose_id`, `simulation`,
+/// `conf_rev`, `ndscom`, `number_of_pcs`); `<value>` is parsed through the same
+/// `FromStr`/hex-or-decimal machinery `PublisherConfigRaw::to_runtime` uses.
+/// Overrides apply in order and are not validated here - run the result
+/// through `init_publisher_goose_frames` afterward to re-validate.
+pub fn apply_overrides(configs: &mut [PublisherConfig], overrides: &[String]) -> Result<()> {
+    for (i, entry) in overrides.iter().enumerate() {
+        let (target, assignment) = match entry.split_once('.') {
+            Some((idx_str, rest)) if !idx_str.is_empty() && idx_str.bytes().all(|b| b.is_ascii_digit()) => {
+                (idx_str.parse::<usize>().context("override index")?, rest)
+            }
+            _ => (0, entry.as_str()),
+        };
+        let (key, value) = assignment
+            .split_once('=')
+            .with_context(|| format!("override {} ('{}'): expected key=value", i, entry))?;
+        let config = configs
+            .get_mut(target)
+            .with_context(|| format!("override {} ('{}'): no config at index {}", i, entry, target))?;
+        apply_config_override(config, key, value)
+            .with_context(|| format!("override {} ('{}')", i, entry))?;
+    }
+    Ok(())
+}
+
+/// Parse and assign one override's `value` onto the named field of `config`.
+fn apply_config_override(config: &mut PublisherConfig, key: &str, value: &str) -> Result<()> {
+    match key {
+        "src_addr" => config.src_addr = value.parse().context("invalid src_addr")?,
+        "dst_addr" => config.dst_addr = value.parse().context("invalid dst_addr")?,
+        "tpid" => {
+            config.tpid = u16::from_str_radix(value.trim_start_matches("0x"), 16)
+                .context("invalid tpid")?
+        }
+        "tci" => {
+            config.tci = u16::from_str_radix(value.trim_start_matches("0x"), 16)
+                .context("invalid tci")?
+        }
+        "appid" => config.appid = parse_appid_str(value)?,
+        "gocb_ref" => config.gocb_ref = value.to_string(),
+        "dat_set" => config.dat_set = value.to_string(),
+        "goose_id" => config.goose_id = value.to_string(),
+        "simulation" => config.simulation = value.parse::<bool>().context("invalid simulation")?,
+        "conf_rev" => config.conf_rev = value.parse::<u32>().context("invalid conf_rev")?,
+        "ndscom" => config.ndscom = value.parse::<bool>().context("invalid ndscom")?,
+        "number_of_pcs" => {
+            config.number_of_pcs = value.parse::<u32>().context("invalid number_of_pcs")?
+        }
+        _ => anyhow::bail!("unknown override key '{}'", key),
+    }
+    Ok(())
+}
+
 // iterate Vector of PublisherConfig to initialize  type PlcPublisherGooseFrame
 pub fn init_publisher_goose_frames(
     configs: &[PublisherConfig],
@@ -456,157 +1267,282 @@ pub fn init_publisher_goose_frames(
     let mut frames = Vec::with_capacity(configs.len());
 
     for (idx, cfg) in configs.iter().enumerate() {
-        // Validate and parse source MAC address
-        let src_mac = match parse_mac(&cfg.src_addr) {
-            Ok(mac) => mac,
-            Err(e) => {
-                error!(
-                    "Config {}: Failed to parse source MAC address '{}': {}",
-                    idx, cfg.src_addr, e
-                );
-                anyhow::bail!(
-                    "Config {}: Failed to parse source MAC address '{}': {}",
-                    idx,
-                    cfg.src_addr,
-                    e
-                );
-            }
-        };
+        frames.push(build_publisher_goose_frame(idx, cfg)?);
+    }
+    info!(
+        "Initialized {} GOOSE publisher frames:{:?}",
+        frames.len(),
+        frames
+    );
 
-        // Validate and parse destination MAC address
-        let dst_mac = match parse_mac(&cfg.dst_addr) {
-            Ok(mac) => mac,
-            Err(e) => {
-                error!(
-                    "Config {}: Failed to parse destination MAC address '{}': {}",
-                    idx, cfg.dst_addr, e
-                );
-                anyhow::bail!(
-                    "Config {}: Failed to parse destination MAC address '{}': {}",
-                    idx,
-                    cfg.dst_addr,
-                    e
-                );
-            }
-        };
+    Ok(frames)
+}
 
-        // Validate APPID range (should be non-zero for GOOSE)
-        if cfg.appid == 0 {
-            anyhow::bail!("Config {}: APPID cannot be 0", idx);
-        }
+/// Validate one `PublisherConfig` and build the `PlcPublisherGooseFrame` it describes,
+/// `stNum`/`sqNum` reset to 0. Shared by `init_publisher_goose_frames` (startup, every
+/// config is "new") and `PublisherConfigStore::reload` (hot reload, only changed configs
+/// call this - unchanged ones keep their live frame untouched so retransmission state
+/// survives).
+///
+/// `idx` is only used to identify which config failed in error messages.
+fn build_publisher_goose_frame(idx: usize, cfg: &PublisherConfig) -> Result<PlcPublisherGooseFrame> {
+    // src_addr/dst_addr are already validated `MacAddr`s - parsing happened once,
+    // at config deserialization. `strict = false` only warns (via `log::warn!`)
+    // when dst_addr falls outside the GOOSE multicast range, so this never
+    // actually fails config loading.
+    cfg.dst_addr.check_goose_multicast(false)?;
+
+    // Validate APPID range (should be non-zero for GOOSE)
+    if cfg.appid == 0 {
+        anyhow::bail!("Config {}: APPID cannot be 0", idx);
+    }
 
-        // Validate number_of_pcs
-        if cfg.number_of_pcs == 0 {
-            anyhow::bail!(
-                "Config {}: number_of_pcs cannot be 0 (goID: {})",
-                idx,
-                cfg.goose_id
-            );
-        }
+    // Validate number_of_pcs
+    if cfg.number_of_pcs == 0 {
+        anyhow::bail!(
+            "Config {}: number_of_pcs cannot be 0 (numDatSetEntries would be 0, goID: {})",
+            idx,
+            cfg.goose_id
+        );
+    }
 
-        // Validate that gocbRef, datSet, and goID are not empty
-        if cfg.gocb_ref.is_empty() {
-            anyhow::bail!("Config {}: gocbRef cannot be empty", idx);
-        }
-        if cfg.dat_set.is_empty() {
-            anyhow::bail!("Config {}: datSet cannot be empty", idx);
-        }
-        if cfg.goose_id.is_empty() {
-            anyhow::bail!("Config {}: goID cannot be empty", idx);
+    // Validate that gocbRef, datSet, and goID are not empty
+    if cfg.gocb_ref.is_empty() {
+        anyhow::bail!("Config {}: gocbRef cannot be empty", idx);
+    }
+    if cfg.dat_set.is_empty() {
+        anyhow::bail!("Config {}: datSet cannot be empty", idx);
+    }
+    if cfg.goose_id.is_empty() {
+        anyhow::bail!("Config {}: goID cannot be empty", idx);
+    }
+    cfg.layout
+        .validate()
+        .with_context(|| format!("Config {}: invalid dataSetLayout (goID: {})", idx, cfg.goose_id))?;
+
+    // Create Ethernet header
+    let mut eth_header = EthernetHeader::default();
+    eth_header.srcAddr = cfg.src_addr.0;
+    eth_header.dstAddr = cfg.dst_addr.0;
+    eth_header.TPID = cfg.tpid.to_be_bytes();
+    eth_header.TCI = cfg.tci.to_be_bytes();
+    eth_header.ehterType = [0x88, 0xB8]; // GOOSE Ethertype
+    eth_header.APPID = cfg.appid.to_be_bytes();
+    //eth_header.length will be assiged later.
+
+    // Create GOOSE PDU
+    let mut goose_pdu = IECGoosePdu::default();
+    goose_pdu.gocbRef = cfg.gocb_ref.clone();
+    goose_pdu.timeAllowedtoLive = 5000; // Example value
+    goose_pdu.datSet = cfg.dat_set.clone();
+    goose_pdu.goID = cfg.goose_id.clone();
+    goose_pdu.t = [0; 8]; // Placeholder for timestamp
+    goose_pdu.stNum = 0;
+    goose_pdu.sqNum = 0;
+    goose_pdu.simulation = cfg.simulation;
+    goose_pdu.confRev = cfg.conf_rev;
+    goose_pdu.ndsCom = cfg.ndscom;
+    goose_pdu.numDatSetEntries = cfg.number_of_pcs * cfg.layout.entries.len() as u32;
+
+    // Initialize allData from the configured per-PCS layout, repeated once per PCS.
+    let expected_data_entries = (cfg.number_of_pcs as usize) * cfg.layout.entries.len();
+    goose_pdu.allData = Vec::with_capacity(expected_data_entries);
+    for _ in 0..cfg.number_of_pcs {
+        for kind in &cfg.layout.entries {
+            goose_pdu.allData.push(match kind {
+                DataSetEntryKind::Boolean => IECData::boolean(false),
+                DataSetEntryKind::Float32 => IECData::float32(0.0),
+            });
         }
+    }
 
-        // Create Ethernet header
-        let mut eth_header = EthernetHeader::default();
-        eth_header.srcAddr = src_mac;
-        eth_header.dstAddr = dst_mac;
-        eth_header.TPID = cfg.tpid.to_be_bytes();
-        eth_header.TCI = cfg.tci.to_be_bytes();
-        eth_header.ehterType = [0x88, 0xB8]; // GOOSE Ethertype
-        eth_header.APPID = cfg.appid.to_be_bytes();
-        //eth_header.length will be assiged later.
+    Ok((eth_header, goose_pdu, cfg.layout.clone()))
+}
 
-        // Create GOOSE PDU
-        let mut goose_pdu = IECGoosePdu::default();
-        goose_pdu.gocbRef = cfg.gocb_ref.clone();
-        goose_pdu.timeAllowedtoLive = 5000; // Example value
-        goose_pdu.datSet = cfg.dat_set.clone();
-        goose_pdu.goID = cfg.goose_id.clone();
-        goose_pdu.t = [0; 8]; // Placeholder for timestamp
-        goose_pdu.stNum = 0;
-        goose_pdu.sqNum = 0;
-        goose_pdu.simulation = cfg.simulation;
-        goose_pdu.confRev = cfg.conf_rev;
-        goose_pdu.ndsCom = cfg.ndscom;
-        goose_pdu.numDatSetEntries = cfg.number_of_pcs * 4;
+/// Runtime-mutable, shareable store for the GOOSE publisher config (`goose_publisher_cfg.json`).
+///
+/// Mirrors `crate::plc::types::PcsConfigStore`: wraps the `Vec<PublisherConfig>`
+/// `load_plc_publisher_config` produces, keyed by `goID`, behind an `Arc<RwLock<..>>` so
+/// `get`/`set`/`remove`/`erase_all` can be called from anywhere (e.g. a management command
+/// handler) while publisher threads keep reading a consistent map. Unlike `PcsConfigStore`,
+/// every mutator persists back to the JSON file immediately, and `reload` additionally diffs
+/// against a live `Vec<PlcPublisherGooseFrame>` so a config-file edit (APPID, MAC, numberOfPcs)
+/// takes effect without restarting the publisher thread.
+#[derive(Clone)]
+pub struct PublisherConfigStore {
+    inner: Arc<RwLock<HashMap<String, PublisherConfig>>>,
+}
 
-        // Initialize allData with proper capacity
-        let expected_data_entries = (cfg.number_of_pcs as usize) * 4; // 2 booleans + 2 floats per PCS
-        goose_pdu.allData = Vec::with_capacity(expected_data_entries);
+impl PublisherConfigStore {
+    /// Load the store from `cfg_path`, same format as `load_plc_publisher_config`.
+    pub fn load(cfg_path: String) -> Result<Self> {
+        let configs = load_plc_publisher_config(cfg_path)?;
+        let map = configs
+            .into_iter()
+            .map(|cfg| (cfg.goose_id.clone(), cfg))
+            .collect();
+        Ok(Self {
+            inner: Arc::new(RwLock::new(map)),
+        })
+    }
 
-        // Add boolean flags for each PCS (P command active, Q command active)
-        for _ in 0..cfg.number_of_pcs {
-            goose_pdu.allData.push(IECData::boolean(false)); // p command active
-            goose_pdu.allData.push(IECData::boolean(false)); // q command active
-        }
+    /// Get a clone of the config entry for `go_id`, if present.
+    pub fn get(&self, go_id: &str) -> Option<PublisherConfig> {
+        self.inner.read().unwrap().get(go_id).cloned()
+    }
+
+    /// Insert or update the config entry keyed by `config.goose_id`, persisting to
+    /// `cfg_path` afterward.
+    ///
+    /// Rejects `config` with the same validation `build_publisher_goose_frame` applies at
+    /// load time (MAC format, non-zero APPID/numberOfPcs, non-empty gocbRef/datSet/goID) so
+    /// a bad entry can't be written to disk only to fail on the next restart.
+    pub fn set<P: AsRef<Path>>(&self, cfg_path: P, config: PublisherConfig) -> Result<()> {
+        build_publisher_goose_frame(0, &config)
+            .with_context(|| format!("Rejected config for goID '{}'", config.goose_id))?;
+        let go_id = config.goose_id.clone();
+        let mut map = self.inner.write().unwrap();
+        map.insert(go_id.clone(), config);
+        Self::persist_locked(&map, &cfg_path)?;
+        drop(map);
+        info!("PublisherConfigStore: upserted config for goID '{}'", go_id);
+        Ok(())
+    }
 
-        // Add float values for each PCS (P command, Q command)
-        for _ in 0..cfg.number_of_pcs {
-            goose_pdu.allData.push(IECData::float32(0.0)); // p command
-            goose_pdu.allData.push(IECData::float32(0.0)); // q command
+    /// Remove the config entry for `go_id`, persisting to `cfg_path` afterward, returning it
+    /// if it existed.
+    pub fn remove<P: AsRef<Path>>(&self, cfg_path: P, go_id: &str) -> Result<Option<PublisherConfig>> {
+        let mut map = self.inner.write().unwrap();
+        let removed = map.remove(go_id);
+        Self::persist_locked(&map, &cfg_path)?;
+        drop(map);
+        if removed.is_some() {
+            info!("PublisherConfigStore: removed config for goID '{}'", go_id);
         }
+        Ok(removed)
+    }
 
-        frames.push((eth_header, goose_pdu));
+    /// Remove every config entry, persisting the now-empty list to `cfg_path` afterward.
+    pub fn erase_all<P: AsRef<Path>>(&self, cfg_path: P) -> Result<()> {
+        let mut map = self.inner.write().unwrap();
+        map.clear();
+        Self::persist_locked(&map, &cfg_path)?;
+        drop(map);
+        info!("PublisherConfigStore: erased all configs");
+        Ok(())
     }
-    info!(
-        "Initialized {} GOOSE publisher frames:{:?}",
-        frames.len(),
-        frames
-    );
 
-    Ok(frames)
-}
+    /// Serialize `map` to `cfg_path` as JSON, same shape `load_plc_publisher_config` reads.
+    ///
+    /// Takes `map` as an already-held lock guard rather than re-acquiring `self.inner` itself,
+    /// so the mutate-then-persist sequence in `set`/`remove`/`erase_all` is a single critical
+    /// section: a concurrent mutator can never observe (or persist) a map state between this
+    /// call's caller mutating it and this call serializing it.
+    fn persist_locked<P: AsRef<Path>>(
+        map: &HashMap<String, PublisherConfig>,
+        cfg_path: P,
+    ) -> Result<()> {
+        let configs: Vec<PublisherConfigRaw> = map.values().map(PublisherConfig::to_raw).collect();
+        let file = File::create(&cfg_path).with_context(|| {
+            format!(
+                "Failed to create publisher config file '{:?}'",
+                cfg_path.as_ref()
+            )
+        })?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &configs).with_context(|| {
+            format!(
+                "Failed to serialize publisher config to '{:?}'",
+                cfg_path.as_ref()
+            )
+        })?;
+        Ok(())
+    }
 
-/// Parse MAC address string into [u8; 6]
-/// Supports formats like "01:0C:CD:01:00:01", "01-0C-CD-01-00-01", "010CCD010001"
-fn parse_mac(s: &str) -> Result<[u8; 6]> {
-    // try split by common separators first
-    let parts: Vec<&str> = s.split(|c| c == ':' || c == '-' || c == '.').collect();
-    if parts.len() == 6 {
-        let mut mac = [0u8; 6];
-        for (i, p) in parts.iter().enumerate() {
-            if p.len() != 2 {
-                error!("MAC part '{}' has wrong length in '{}'", p, s);
-                anyhow::bail!("MAC part '{}' has wrong length", p);
-            }
-            mac[i] = match u8::from_str_radix(p, 16) {
-                Ok(byte) => byte,
-                Err(e) => {
-                    error!("Invalid hex '{}' in MAC address '{}': {}", p, s, e);
-                    anyhow::bail!("Invalid hex in '{}': {}", p, e);
-                }
-            };
-        }
-        return Ok(mac);
-    }
-
-    // otherwise strip everything except hex digits and try parse as 12 hex chars
-    let s_hex: String = s.chars().filter(|c| c.is_ascii_hexdigit()).collect();
-    if s_hex.len() == 12 {
-        let mut mac = [0u8; 6];
-        for i in 0..6 {
-            let byte = &s_hex[2 * i..2 * i + 2];
-            mac[i] = match u8::from_str_radix(byte, 16) {
-                Ok(b) => b,
-                Err(e) => {
-                    error!("Invalid hex '{}' in MAC address '{}': {}", byte, s, e);
-                    anyhow::bail!("Invalid hex in '{}': {}", byte, e);
+    /// Re-read `cfg_path`, diff the new configs against the *live* `frames` (not the
+    /// store's cached map, which `set`/`remove` may already have moved past what
+    /// `frames` reflects), and rebuild only the entries that actually changed -
+    /// everything else (including each frame's `stNum`/`sqNum`) is left untouched so the
+    /// retransmission thread's sequencing isn't disrupted by an unrelated edit
+    /// elsewhere in the file.
+    ///
+    /// Every config is parsed, validated and rebuilt into a candidate frame before
+    /// `frames` or the store's map is touched at all, so a bad config partway through
+    /// the file leaves both untouched rather than half-applied. A config whose goID is
+    /// new gets its freshly-built frame appended; a goID present before but missing from
+    /// the reloaded file has its frame dropped. A changed frame has `confRev` bumped by
+    /// one from its prior value per IEC 61850 configuration-revision semantics.
+    pub fn reload<P: AsRef<Path>>(
+        &self,
+        cfg_path: P,
+        frames: &mut Vec<PlcPublisherGooseFrame>,
+    ) -> Result<()> {
+        let new_configs = load_plc_publisher_config(
+            cfg_path
+                .as_ref()
+                .to_str()
+                .context("cfg_path is not valid UTF-8")?
+                .to_string(),
+        )?;
+        if new_configs.is_empty() {
+            anyhow::bail!("Publisher configuration is empty");
+        }
+
+        let mut rebuilt: Vec<(String, PlcPublisherGooseFrame)> = Vec::new();
+        for (idx, cfg) in new_configs.iter().enumerate() {
+            let mut candidate = build_publisher_goose_frame(idx, cfg)?;
+
+            if let Some((existing_eth, existing_pdu, existing_layout)) =
+                frames.iter().find(|(_, pdu, _)| pdu.goID == cfg.goose_id)
+            {
+                let unchanged = existing_eth.srcAddr == candidate.0.srcAddr
+                    && existing_eth.dstAddr == candidate.0.dstAddr
+                    && existing_eth.TPID == candidate.0.TPID
+                    && existing_eth.TCI == candidate.0.TCI
+                    && existing_eth.APPID == candidate.0.APPID
+                    && existing_pdu.gocbRef == candidate.1.gocbRef
+                    && existing_pdu.datSet == candidate.1.datSet
+                    && existing_pdu.simulation == candidate.1.simulation
+                    && existing_pdu.ndsCom == candidate.1.ndsCom
+                    && existing_pdu.numDatSetEntries == candidate.1.numDatSetEntries
+                    && *existing_layout == candidate.2;
+                if unchanged {
+                    continue; // leave the live frame (stNum/sqNum/confRev included) alone
                 }
-            };
+                // A real config change (MAC/VLAN/dataset/layout) is a new GOOSE state, not
+                // a continuation of the old one: `candidate` keeps the stNum=0/sqNum=0 it
+                // was built with, and only confRev carries forward (bumped).
+                candidate.1.confRev = existing_pdu.confRev + 1;
+            }
+
+            rebuilt.push((cfg.goose_id.clone(), candidate));
+        }
+
+        // Every config in the new file parsed, validated and (if changed) rebuilt -
+        // now it's safe to apply.
+        frames.retain(|(_, pdu, _)| new_configs.iter().any(|cfg| cfg.goose_id == pdu.goID));
+        for (go_id, new_frame) in rebuilt {
+            match frames.iter_mut().find(|(_, pdu, _)| pdu.goID == go_id) {
+                Some(existing) => *existing = new_frame,
+                None => frames.push(new_frame),
+            }
         }
-        return Ok(mac);
-    }
 
-    anyhow::bail!("invalid MAC format: {}", s)
+        // `cfg.conf_rev` still carries whatever was on disk; for any goID this reload
+        // actually rebuilt, replace it with the value just applied to the live frame so
+        // the store's map - and whatever `set`/`remove` persist back from it later -
+        // can't regress a GOOSE subscriber's confRev across a restart or later reload.
+        *self.inner.write().unwrap() = new_configs
+            .into_iter()
+            .map(|mut cfg| {
+                if let Some((_, pdu, _)) = frames.iter().find(|(_, pdu, _)| pdu.goID == cfg.goose_id) {
+                    cfg.conf_rev = pdu.confRev;
+                }
+                (cfg.goose_id.clone(), cfg)
+            })
+            .collect();
+        info!("PublisherConfigStore: reloaded configuration");
+        Ok(())
+    }
 }
 
 //assign  StPlcCmdAll data to PlcPublisherGooseFrame in case of new udp command received
@@ -625,7 +1561,13 @@ impl StPlcCmdAll {
             frame.1.sqNum = 0;
             frame.1.allData.clear();
 
-            let entries_count = (frame.1.numDatSetEntries / 4) as usize; // Each PCS has 4 entries (2 booleans + 2 floats)
+            let unit_width = frame.2.entries.len();
+            if unit_width == 0 {
+                log::error!("goID '{}': dataSetLayout has no entries, skipping", frame.1.goID);
+                continue;
+            }
+            let entries_count = (frame.1.numDatSetEntries as usize) / unit_width;
+
             // Check if we have enough commands
             if cmd_position + entries_count > self.pcs_cmds.len() {
                 log::error!(
@@ -636,43 +1578,31 @@ impl StPlcCmdAll {
                 break;
             }
 
-            // First pass: Add boolean flags for each PCS command
+            // For each PCS command, fill in its slice of `allData` per the
+            // configured layout: booleans per `command_codes`, floats from
+            // `pcs_active_power`/`pcs_reactive_power` in the order float
+            // entries appear (cycling if the layout has more than two).
             for i in 0..entries_count {
-                let cmd_index = i + cmd_position;
-                // info!(
-                //     "Processing PCS command {}: protocol={}",
-                //     cmd_index, self.pcs_cmds[cmd_index].protocol
-                // );
-                let cmd = &self.pcs_cmds[cmd_index].protocol;
-                match cmd {
-                    10 => {
-                        frame.1.allData.push(IECData::boolean(true)); // P command active
-                        frame.1.allData.push(IECData::boolean(false)); // Q command inactive
-                    }
-                    20 => {
-                        frame.1.allData.push(IECData::boolean(false)); // P command inactive
-                        frame.1.allData.push(IECData::boolean(true)); // Q command active
-                    }
-                    30 => {
-                        frame.1.allData.push(IECData::boolean(true)); // P command active
-                        frame.1.allData.push(IECData::boolean(true)); // Q command active
-                    }
-                    _ => {
-                        frame.1.allData.push(IECData::boolean(false)); // P command inactive
-                        frame.1.allData.push(IECData::boolean(false)); // Q command inactive
+                let cmd = &self.pcs_cmds[i + cmd_position];
+                let asserted = frame.2.command_codes.get(&cmd.protocol);
+                let floats = [cmd.pcs_active_power, cmd.pcs_reactive_power];
+                let mut bool_idx = 0;
+                let mut float_idx = 0;
+                for kind in &frame.2.entries {
+                    match kind {
+                        DataSetEntryKind::Boolean => {
+                            let value = asserted.is_some_and(|idxs| idxs.contains(&bool_idx));
+                            frame.1.allData.push(IECData::boolean(value));
+                            bool_idx += 1;
+                        }
+                        DataSetEntryKind::Float32 => {
+                            let value = floats[float_idx % floats.len()];
+                            frame.1.allData.push(IECData::float32(value));
+                            float_idx += 1;
+                        }
                     }
                 }
             }
-
-            // Second pass: Add power values for each PCS command
-            for i in 0..entries_count {
-                let cmd = &self.pcs_cmds[i + cmd_position];
-                frame.1.allData.push(IECData::float32(cmd.pcs_active_power));
-                frame
-                    .1
-                    .allData
-                    .push(IECData::float32(cmd.pcs_reactive_power));
-            }
             cmd_position += entries_count;
         }
     }
@@ -684,8 +1614,8 @@ mod tests {
 
     fn create_valid_config() -> PublisherConfig {
         PublisherConfig {
-            src_addr: "01:0C:CD:01:00:01".to_string(),
-            dst_addr: "01:0C:CD:FF:FF:FF".to_string(),
+            src_addr: "01:0C:CD:01:00:01".parse().unwrap(),
+            dst_addr: "01:0C:CD:FF:FF:FF".parse().unwrap(),
             tpid: 0x8100,
             tci: 0x8002,
             appid: 0x0008,
@@ -696,9 +1626,95 @@ mod tests {
             conf_rev: 1,
             ndscom: false,
             number_of_pcs: 2,
+            layout: DataSetLayout::legacy(),
         }
     }
 
+    #[test]
+    fn test_publisher_config_raw_appid_accepts_hex_or_decimal() {
+        let hex: PublisherConfigRaw =
+            serde_json::from_str(r#"{"srcAddr":"01:0C:CD:01:00:01","dstAddr":"01:0C:CD:FF:FF:FF","TPID":"0x8100","TCI":"0x8002","APPID":"0x3000","gocbRef":"g","datSet":"d","goID":"id","simulation":"false","confRev":"1","ndsCom":"false","numberOfPcs":"1"}"#)
+                .expect("hex APPID should parse");
+        assert_eq!(hex.appid, 0x3000);
+
+        let decimal: PublisherConfigRaw =
+            serde_json::from_str(r#"{"srcAddr":"01:0C:CD:01:00:01","dstAddr":"01:0C:CD:FF:FF:FF","TPID":"0x8100","TCI":"0x8002","APPID":"12288","gocbRef":"g","datSet":"d","goID":"id","simulation":"false","confRev":"1","ndsCom":"false","numberOfPcs":"1"}"#)
+                .expect("decimal string APPID should parse");
+        assert_eq!(decimal.appid, 12288);
+
+        let number: PublisherConfigRaw =
+            serde_json::from_str(r#"{"srcAddr":"01:0C:CD:01:00:01","dstAddr":"01:0C:CD:FF:FF:FF","TPID":"0x8100","TCI":"0x8002","APPID":12288,"gocbRef":"g","datSet":"d","goID":"id","simulation":"false","confRev":"1","ndsCom":"false","numberOfPcs":"1"}"#)
+                .expect("bare numeric APPID should parse");
+        assert_eq!(number.appid, 12288);
+    }
+
+    #[test]
+    fn test_apply_overrides_unindexed_targets_first_config() {
+        let mut configs = vec![create_valid_config()];
+        apply_overrides(
+            &mut configs,
+            &[
+                "appid=0x3000".to_string(),
+                "number_of_pcs=3".to_string(),
+                "dst_addr=01-0C-CD-01-00-02".to_string(),
+                "gocb_ref=Other/LLN0$GO$Gcb9".to_string(),
+            ],
+        )
+        .expect("overrides should apply");
+
+        assert_eq!(configs[0].appid, 0x3000);
+        assert_eq!(configs[0].number_of_pcs, 3);
+        assert_eq!(configs[0].dst_addr.0, [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x02]);
+        assert_eq!(configs[0].gocb_ref, "Other/LLN0$GO$Gcb9");
+    }
+
+    #[test]
+    fn test_apply_overrides_indexed_targets_later_config() {
+        let mut configs = vec![create_valid_config(), create_valid_config()];
+        apply_overrides(&mut configs, &["1.number_of_pcs=5".to_string()]).unwrap();
+
+        assert_eq!(configs[0].number_of_pcs, 2, "untargeted config is untouched");
+        assert_eq!(configs[1].number_of_pcs, 5);
+    }
+
+    #[test]
+    fn test_apply_overrides_decimal_appid() {
+        let mut configs = vec![create_valid_config()];
+        apply_overrides(&mut configs, &["appid=12288".to_string()]).unwrap();
+        assert_eq!(configs[0].appid, 12288);
+    }
+
+    #[test]
+    fn test_apply_overrides_rejects_unknown_key() {
+        let mut configs = vec![create_valid_config()];
+        let result = apply_overrides(&mut configs, &["bogus_field=1".to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_overrides_rejects_missing_equals() {
+        let mut configs = vec![create_valid_config()];
+        let result = apply_overrides(&mut configs, &["appid".to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_overrides_rejects_out_of_range_index() {
+        let mut configs = vec![create_valid_config()];
+        let result = apply_overrides(&mut configs, &["3.appid=0x3000".to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_overrides_then_init_revalidates() {
+        let mut configs = vec![create_valid_config()];
+        apply_overrides(&mut configs, &["appid=0".to_string()]).unwrap();
+
+        let result = init_publisher_goose_frames(&configs);
+        assert!(result.is_err(), "overridden-invalid config should fail validation");
+        assert!(result.unwrap_err().to_string().contains("APPID"));
+    }
+
     #[test]
     fn test_init_publisher_goose_frames_success() {
         let configs = vec![create_valid_config()];
@@ -727,32 +1743,26 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("empty"));
     }
 
-    #[test]
-    fn test_init_publisher_goose_frames_invalid_src_mac() {
-        let mut config = create_valid_config();
-        config.src_addr = "invalid:mac:addr".to_string();
+    // Invalid MAC addresses can no longer reach `init_publisher_goose_frames` at
+    // all - `PublisherConfig::src_addr`/`dst_addr` are `MacAddr`, so parsing (and
+    // rejection of a bad string) happens once, when `PublisherConfigRaw` is
+    // deserialized. See `test_publisher_config_raw_rejects_invalid_src_mac` and
+    // `test_publisher_config_raw_rejects_invalid_dst_mac` below.
 
-        let result = init_publisher_goose_frames(&[config]);
-        assert!(result.is_err(), "Should fail with invalid source MAC");
-        let err_msg = result.unwrap_err().to_string();
-        assert!(
-            err_msg.contains("source MAC"),
-            "Error should mention source MAC"
+    #[test]
+    fn test_publisher_config_raw_rejects_invalid_src_mac() {
+        let result: std::result::Result<PublisherConfigRaw, _> = serde_json::from_str(
+            r#"{"srcAddr":"invalid:mac:addr","dstAddr":"01:0C:CD:FF:FF:FF","TPID":"0x8100","TCI":"0x8002","APPID":"0x0008","gocbRef":"g","datSet":"d","goID":"id","simulation":"false","confRev":"1","ndsCom":"false","numberOfPcs":"1"}"#,
         );
+        assert!(result.is_err(), "Should fail with invalid source MAC");
     }
 
     #[test]
-    fn test_init_publisher_goose_frames_invalid_dst_mac() {
-        let mut config = create_valid_config();
-        config.dst_addr = "ZZ:ZZ:ZZ:ZZ:ZZ:ZZ".to_string();
-
-        let result = init_publisher_goose_frames(&[config]);
-        assert!(result.is_err(), "Should fail with invalid destination MAC");
-        let err_msg = result.unwrap_err().to_string();
-        assert!(
-            err_msg.contains("destination MAC"),
-            "Error should mention destination MAC"
+    fn test_publisher_config_raw_rejects_invalid_dst_mac() {
+        let result: std::result::Result<PublisherConfigRaw, _> = serde_json::from_str(
+            r#"{"srcAddr":"01:0C:CD:01:00:01","dstAddr":"ZZ:ZZ:ZZ:ZZ:ZZ:ZZ","TPID":"0x8100","TCI":"0x8002","APPID":"0x0008","gocbRef":"g","datSet":"d","goID":"id","simulation":"false","confRev":"1","ndsCom":"false","numberOfPcs":"1"}"#,
         );
+        assert!(result.is_err(), "Should fail with invalid destination MAC");
     }
 
     #[test]
@@ -817,7 +1827,7 @@ mod tests {
     fn test_init_publisher_goose_frames_multiple_configs() {
         let config1 = create_valid_config();
         let mut config2 = create_valid_config();
-        config2.src_addr = "01:0C:CD:01:00:02".to_string();
+        config2.src_addr = "01:0C:CD:01:00:02".parse().unwrap();
         config2.goose_id = "TestDevice2/LLN0.Gcb2".to_string();
         config2.number_of_pcs = 3;
 
@@ -846,36 +1856,346 @@ mod tests {
         );
     }
 
+    // Unique per-test scratch file under the OS temp dir, since `PublisherConfigStore`
+    // persists through a real path and cargo test runs tests in parallel threads.
+    fn temp_cfg_path(test_name: &str) -> std::path::PathBuf {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        std::env::temp_dir().join(format!(
+            "publisher_config_store_{}_{}_{}.json",
+            std::process::id(),
+            test_name,
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ))
+    }
+
+    fn write_configs(path: &std::path::Path, configs: &[PublisherConfig]) {
+        let raw: Vec<PublisherConfigRaw> = configs.iter().map(PublisherConfig::to_raw).collect();
+        let file = File::create(path).expect("create temp config file");
+        serde_json::to_writer_pretty(BufWriter::new(file), &raw).expect("write temp config file");
+    }
+
+    #[test]
+    fn test_publisher_config_store_get_set_remove_roundtrip() {
+        let path = temp_cfg_path("get_set_remove");
+        write_configs(&path, &[create_valid_config()]);
+
+        let store = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        assert!(store.get("TestDevice/LLN0.Gcb1").is_some());
+        assert!(store.get("missing").is_none());
+
+        let mut second = create_valid_config();
+        second.goose_id = "TestDevice2/LLN0.Gcb2".to_string();
+        store.set(&path, second.clone()).unwrap();
+        assert!(store.get("TestDevice2/LLN0.Gcb2").is_some());
+
+        // persisted, so reloading from disk sees both entries
+        let reloaded = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        assert!(reloaded.get("TestDevice/LLN0.Gcb1").is_some());
+        assert!(reloaded.get("TestDevice2/LLN0.Gcb2").is_some());
+
+        let removed = store.remove(&path, "TestDevice/LLN0.Gcb1").unwrap();
+        assert!(removed.is_some());
+        assert!(store.get("TestDevice/LLN0.Gcb1").is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_publisher_config_store_set_rejects_invalid_config() {
+        let path = temp_cfg_path("set_rejects_invalid");
+        write_configs(&path, &[create_valid_config()]);
+
+        let store = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        let mut bad = create_valid_config();
+        bad.goose_id = "Bad/LLN0.Gcb".to_string();
+        bad.appid = 0;
+
+        assert!(store.set(&path, bad).is_err());
+        assert!(store.get("Bad/LLN0.Gcb").is_none());
+
+        // rejected entry must not have been written to disk either
+        let reloaded = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        assert!(reloaded.get("Bad/LLN0.Gcb").is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_publisher_config_store_reload_rejects_empty_config() {
+        let path = temp_cfg_path("reload_rejects_empty");
+        write_configs(&path, &[create_valid_config()]);
+
+        let store = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        let mut frames = init_publisher_goose_frames(&[create_valid_config()]).unwrap();
+
+        write_configs(&path, &[]);
+        assert!(store.reload(&path, &mut frames).is_err());
+        assert_eq!(frames.len(), 1, "frames must be untouched on a rejected reload");
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_publisher_config_store_erase_all() {
+        let path = temp_cfg_path("erase_all");
+        write_configs(&path, &[create_valid_config()]);
+
+        let store = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        store.erase_all(&path).unwrap();
+        assert!(store.get("TestDevice/LLN0.Gcb1").is_none());
+
+        let reloaded = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        assert!(reloaded.get("TestDevice/LLN0.Gcb1").is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_publisher_config_store_reload_rebuilds_only_changed_frame() {
+        let path = temp_cfg_path("reload_changed");
+        let unchanged = create_valid_config();
+        let mut changed = create_valid_config();
+        changed.goose_id = "TestDevice2/LLN0.Gcb2".to_string();
+        write_configs(&path, &[unchanged.clone(), changed.clone()]);
+
+        let store = PublisherConfigStore::load(path.to_str().unwrap().to_string()).unwrap();
+        let mut frames =
+            init_publisher_goose_frames(&[unchanged.clone(), changed.clone()]).unwrap();
+
+        // Simulate both frames having advanced retransmission state already.
+        for frame in frames.iter_mut() {
+            frame.1.stNum = 7;
+            frame.1.sqNum = 3;
+        }
+
+        // Edit only `changed`'s APPID on disk.
+        let mut changed_on_disk = changed.clone();
+        changed_on_disk.appid = 0x0099;
+        write_configs(&path, &[unchanged.clone(), changed_on_disk.clone()]);
+
+        store.reload(&path, &mut frames).unwrap();
+
+        let unchanged_frame = frames
+            .iter()
+            .find(|(_, pdu, _)| pdu.goID == unchanged.goose_id)
+            .unwrap();
+        assert_eq!(unchanged_frame.1.stNum, 7, "untouched config keeps stNum");
+        assert_eq!(unchanged_frame.1.sqNum, 3, "untouched config keeps sqNum");
+        assert_eq!(unchanged_frame.1.confRev, unchanged.conf_rev);
+
+        let changed_frame = frames
+            .iter()
+            .find(|(_, pdu, _)| pdu.goID == changed.goose_id)
+            .unwrap();
+        assert_eq!(changed_frame.1.stNum, 0, "rebuilt config resets stNum");
+        assert_eq!(changed_frame.1.sqNum, 0, "rebuilt config resets sqNum");
+        assert_eq!(
+            changed_frame.1.confRev,
+            changed.conf_rev + 1,
+            "confRev bumps on rebuild"
+        );
+        assert_eq!(changed_frame.0.APPID, 0x0099u16.to_be_bytes());
+
+        std::fs::remove_file(&path).ok();
+    }
+
     #[test]
-    fn test_parse_mac_colon_format() {
-        let result = parse_mac("01:0C:CD:01:00:01");
+    fn test_macaddr_from_str_colon_format() {
+        let result: Result<MacAddr> = "01:0C:CD:01:00:01".parse();
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01]);
+        assert_eq!(result.unwrap().0, [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01]);
     }
 
     #[test]
-    fn test_parse_mac_dash_format() {
-        let result = parse_mac("01-0C-CD-01-00-01");
+    fn test_macaddr_from_str_dash_format() {
+        let result: Result<MacAddr> = "01-0C-CD-01-00-01".parse();
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01]);
+        assert_eq!(result.unwrap().0, [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01]);
     }
 
     #[test]
-    fn test_parse_mac_no_separator() {
-        let result = parse_mac("010CCD010001");
+    fn test_macaddr_from_str_no_separator() {
+        let result: Result<MacAddr> = "010CCD010001".parse();
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01]);
+        assert_eq!(result.unwrap().0, [0x01, 0x0C, 0xCD, 0x01, 0x00, 0x01]);
     }
 
     #[test]
-    fn test_parse_mac_invalid_hex() {
-        let result = parse_mac("ZZ:0C:CD:01:00:01");
+    fn test_macaddr_from_str_invalid_hex() {
+        let result: Result<MacAddr> = "ZZ:0C:CD:01:00:01".parse();
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_parse_mac_wrong_length() {
-        let result = parse_mac("01:0C:CD:01:00");
+    fn test_macaddr_from_str_wrong_length() {
+        let result: Result<MacAddr> = "01:0C:CD:01:00".parse();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_macaddr_display_is_canonical_colon_form() {
+        let mac: MacAddr = "01-0c-cd-01-00-01".parse().unwrap();
+        assert_eq!(mac.to_string(), "01:0C:CD:01:00:01");
+    }
+
+    #[test]
+    fn test_macaddr_goose_multicast_range() {
+        let in_range: MacAddr = "01:0C:CD:01:00:01".parse().unwrap();
+        assert!(in_range.is_goose_multicast());
+        assert!(in_range.check_goose_multicast(true).is_ok());
+
+        let out_of_range: MacAddr = "01:0C:CD:FF:FF:FF".parse().unwrap();
+        assert!(!out_of_range.is_goose_multicast());
+        assert!(out_of_range.check_goose_multicast(false).is_ok());
+        assert!(out_of_range.check_goose_multicast(true).is_err());
+    }
+
+    fn sample_cmd(pcs_logical_id: u16) -> StPlcCmdPub {
+        StPlcCmdPub {
+            pcs_logical_id,
+            protocol: 10,
+            pcs_active_power: 12.5,
+            pcs_reactive_power: -4.25,
+            spare: [9u8; 16],
+        }
+    }
+
+    #[test]
+    fn test_stplccmdpub_len_written_and_write_to_slice() {
+        let cmd = sample_cmd(7);
+        assert_eq!(cmd.len_written(), 27);
+
+        let mut buf = [0u8; 27];
+        assert_eq!(cmd.write_to_slice(&mut buf).unwrap(), 27);
+        assert_eq!(buf[0], cmd.protocol);
+        assert_eq!(u16::from_le_bytes([buf[1], buf[2]]), cmd.pcs_logical_id);
+    }
+
+    #[test]
+    fn test_stplccmdpub_write_to_slice_rejects_short_buffer() {
+        let cmd = sample_cmd(7);
+        let mut buf = [0u8; 26];
+        assert!(cmd.write_to_slice(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_stplccmdall_len_written() {
+        let image = StPlcCmdAll {
+            pcs_cmds: vec![sample_cmd(1), sample_cmd(2)],
+            ..StPlcCmdAll::default()
+        };
+        assert_eq!(image.len_written(), 29 + 2 * 27);
+    }
+
+    #[test]
+    fn test_stplccmdall_serialize_rejects_short_buffer() {
+        let image = StPlcCmdAll {
+            pcs_cmds: vec![sample_cmd(1)],
+            ..StPlcCmdAll::default()
+        };
+        let mut buf = vec![0u8; image.len_written() - 1];
+        assert!(image.serialize_stplccmdall(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_stplccmdall_round_trip_via_to_vec() {
+        let image = StPlcCmdAll {
+            protocol: 20,
+            nanotimer: 123_456_789,
+            number_of_pcs: 3,
+            spare: [3u8; 16],
+            pcs_cmds: vec![sample_cmd(5), sample_cmd(1), sample_cmd(9)],
+        };
+
+        let bytes = image.to_vec();
+        assert_eq!(bytes.len(), image.len_written());
+
+        // deserialize_stplccmdall_unsafe is the deserializer whose layout
+        // (29-byte header including a pcs_cmds count field) matches
+        // len_written/write_to_slice; it's safe here because `bytes` was
+        // produced by write_to_slice itself, so length/alignment hold.
+        let decoded = unsafe { deserialize_stplccmdall_unsafe(&bytes).expect("valid datagram") };
+        assert_eq!(decoded, image);
+    }
+
+    #[test]
+    fn test_stplccmdall_round_trip_empty_commands() {
+        let image = StPlcCmdAll::default();
+        let bytes = image.to_vec();
+        let decoded = unsafe { deserialize_stplccmdall_unsafe(&bytes).expect("valid datagram") };
+        assert_eq!(decoded, image);
+    }
+
+    #[test]
+    fn test_stplcmgmtcmd_round_trip() {
+        let cmd = StPlcMgmtCmd::new(PlcMgmtOp::SetEnabled, "TestDevice/LLN0.Gcb1", false);
+        let bytes = cmd.to_vec();
+        let decoded = StPlcMgmtCmd::deserialize(&bytes).expect("valid mgmt datagram");
+        assert_eq!(decoded, cmd);
+    }
+
+    #[test]
+    fn test_parse_udp_datagram_dispatches_on_protocol_byte() {
+        let cmd_all = StPlcCmdAll::default();
+        match parse_udp_datagram(&cmd_all.to_vec()).unwrap() {
+            PlcDatagram::Commands(decoded) => assert_eq!(decoded, cmd_all),
+            PlcDatagram::Mgmt(_) => panic!("expected Commands"),
+        }
+
+        let mgmt = StPlcMgmtCmd::new(PlcMgmtOp::QueryState, "TestDevice/LLN0.Gcb1", false);
+        match parse_udp_datagram(&mgmt.to_vec()).unwrap() {
+            PlcDatagram::Mgmt(decoded) => assert_eq!(decoded, mgmt),
+            PlcDatagram::Commands(_) => panic!("expected Mgmt"),
+        }
+
+        assert!(parse_udp_datagram(&[]).is_err());
+        assert!(parse_udp_datagram(&[0xFF, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_mgmt_reset_counters_and_toggle_simulation() {
+        let mut frames = init_publisher_goose_frames(&[create_valid_config()]).unwrap();
+        frames[0].1.stNum = 7;
+        frames[0].1.sqNum = 3;
+        let mut runtime = PlcMgmtRuntimeState::new();
+
+        let reset = StPlcMgmtCmd::new(PlcMgmtOp::ResetCounters, "TestDevice/LLN0.Gcb1", false);
+        let resp = reset.apply(&mut frames, &mut runtime);
+        assert_eq!(resp.status, 0);
+        assert_eq!(resp.st_num, 0);
+        assert_eq!(resp.sq_num, 0);
+
+        let toggle = StPlcMgmtCmd::new(PlcMgmtOp::ToggleSimulation, "TestDevice/LLN0.Gcb1", false);
+        let resp = toggle.apply(&mut frames, &mut runtime);
+        assert!(resp.simulation);
+        let resp = toggle.apply(&mut frames, &mut runtime);
+        assert!(!resp.simulation);
+    }
+
+    #[test]
+    fn test_mgmt_set_enabled_tracked_in_runtime_state() {
+        let mut frames = init_publisher_goose_frames(&[create_valid_config()]).unwrap();
+        let mut runtime = PlcMgmtRuntimeState::new();
+        assert!(runtime.is_enabled("TestDevice/LLN0.Gcb1"));
+
+        let disable = StPlcMgmtCmd::new(PlcMgmtOp::SetEnabled, "TestDevice/LLN0.Gcb1", false);
+        let resp = disable.apply(&mut frames, &mut runtime);
+        assert!(!resp.enabled);
+        assert!(!runtime.is_enabled("TestDevice/LLN0.Gcb1"));
+
+        let enable = StPlcMgmtCmd::new(PlcMgmtOp::SetEnabled, "TestDevice/LLN0.Gcb1", true);
+        let resp = enable.apply(&mut frames, &mut runtime);
+        assert!(resp.enabled);
+        assert!(runtime.is_enabled("TestDevice/LLN0.Gcb1"));
+    }
+
+    #[test]
+    fn test_mgmt_unknown_goid_returns_error_response() {
+        let mut frames = init_publisher_goose_frames(&[create_valid_config()]).unwrap();
+        let mut runtime = PlcMgmtRuntimeState::new();
+        let cmd = StPlcMgmtCmd::new(PlcMgmtOp::QueryState, "no/such/goID", false);
+        let resp = cmd.apply(&mut frames, &mut runtime);
+        assert_eq!(resp.status, 1);
+    }
 }
@@ -1,42 +1,260 @@
+use crate::goose::buffer_pool::{BufferPool, PooledBuffer, BUFFER_SIZE};
 use crate::goose::pdu::encodeGooseFrame;
-use crate::goose::types::{EthernetHeader, IECGoosePdu};
+use crate::goose::types::{EthernetHeader, IECData, IECGoosePdu};
+use crate::pcs::publisher::RetransmissionProfile;
 use crate::threads::retransmit_signal::RetransmitSignal;
+use anyhow::Result;
 use crossbeam_channel::Sender;
 use log::{error, info, warn};
 use std::sync::atomic::{AtomicBool, Ordering};
 
+use std::collections::VecDeque;
 use std::sync::{Arc, RwLock};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
-const GOOSE_BUFFER_SIZE: usize = 1500;
+/// A GOOSE frame driven by the retransmit thread, carrying its own IEC 61850
+/// retransmission curve (profile) and interval/deadline state so each PCS type
+/// can be retransmitted on its own curve instead of one shared interval.
+pub struct RetransmitFrame {
+    pub header: EthernetHeader,
+    pub pdu: IECGoosePdu,
+    pub profile: RetransmissionProfile,
+    /// Interval (ms) this frame is currently being retransmitted at.
+    current_interval_ms: u64,
+    /// Next wall-clock time this frame is due to be (re)sent.
+    next_due: Instant,
+}
+
+impl RetransmitFrame {
+    pub fn new(header: EthernetHeader, pdu: IECGoosePdu, profile: RetransmissionProfile) -> Self {
+        Self {
+            header,
+            pdu,
+            current_interval_ms: profile.t_min_ms,
+            profile,
+            next_due: Instant::now(),
+        }
+    }
+
+    /// Next wall-clock time this frame is due to be (re)sent.
+    pub fn next_due(&self) -> Instant {
+        self.next_due
+    }
+}
+
+/// Polling ceiling used when there are no frames yet to derive a wait target from.
+const DEFAULT_POLL_MS: u64 = 1000;
+
+/// Number of timeout-driven iterations kept for the jitter correction window.
+const JITTER_WINDOW_SIZE: usize = 8;
+/// Samples whose magnitude exceeds this are treated as one-off preemptions
+/// (not a timing trend) and excluded from the correction estimate.
+const JITTER_DEGLITCH_THRESHOLD_MS: i64 = 50;
+
+/// Closed-loop compensator for OS scheduling jitter in the retransmit loop.
+///
+/// Keeps a short ring buffer of the signed error (`actual_elapsed - target`)
+/// for timeout-driven iterations and uses the median of the non-outlier
+/// samples to correct the next `wait_timeout` target, so the effective period
+/// converges on the intended 2/4/8ms curve despite per-wakeup latency. A
+/// median-of-window estimator is used rather than a mean so a single long
+/// stall cannot poison the correction.
+struct JitterCompensator {
+    samples: VecDeque<i64>,
+}
 
-/// Spawns the retransmission thread that implements exponential backoff
-/// 
-/// This thread:
-/// - Sends GOOSE frames immediately on first iteration or reset
-/// - Implements exponential backoff: 2ms → 4ms → 8ms → ... → 5000ms
-/// - Resets interval when new PLC commands arrive (reset_signal)
-/// 
+impl JitterCompensator {
+    fn new() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(JITTER_WINDOW_SIZE),
+        }
+    }
+
+    /// Drop all history. Call whenever the interval restarts (new PLC data).
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Record the signed error (in ms) of a timeout-driven iteration.
+    fn record(&mut self, actual_elapsed: Duration, target: Duration) {
+        if self.samples.len() == JITTER_WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples
+            .push_back(actual_elapsed.as_millis() as i64 - target.as_millis() as i64);
+    }
+
+    /// Median (in ms) of the samples within the deglitch threshold, or 0 if
+    /// every sample was discarded as an outlier (or the window is empty).
+    fn correction_ms(&self) -> i64 {
+        let mut survivors: Vec<i64> = self
+            .samples
+            .iter()
+            .copied()
+            .filter(|err| err.abs() <= JITTER_DEGLITCH_THRESHOLD_MS)
+            .collect();
+        if survivors.is_empty() {
+            return 0;
+        }
+        survivors.sort_unstable();
+        survivors[survivors.len() / 2]
+    }
+}
+
+/// Apply `correction_ms` to `interval_ms`, clamped so the result stays
+/// positive and does not exceed the current interval.
+fn corrected_target_ms(interval_ms: u64, correction_ms: i64) -> u64 {
+    (interval_ms as i64 - correction_ms).clamp(1, interval_ms.max(1) as i64) as u64
+}
+
+/// Lease a buffer from `pool`, encode `header`/`pdu` into it, and set its
+/// length to the encoded size. Returns `None` (logging) if the pool is exhausted.
+pub(crate) fn encode_into_pooled_buffer(
+    pool: &BufferPool,
+    header: &mut EthernetHeader,
+    pdu: &mut IECGoosePdu,
+) -> Option<PooledBuffer> {
+    let mut pooled = pool.acquire()?;
+    pooled.set_len(BUFFER_SIZE);
+    let goose_frame_size = encodeGooseFrame(header, pdu, pooled.as_mut_slice(), 0);
+    pooled.set_len(goose_frame_size);
+    Some(pooled)
+}
+
+/// Update one frame's stNum/sqNum/timeAllowedToLive/interval/next_due for this
+/// retransmit and encode it into a pooled buffer, without sending it anywhere.
+/// Called only when the frame is due: either a PLC data reset (all frames) or
+/// its own `next_due` elapsed.
+///
+/// Split out from [`retransmit_frame`] so a caller that owns its send path
+/// directly (e.g. [`crate::goose::handle_send::handle_send`], which writes to
+/// a `DataLinkSender` rather than a `Sender<PooledBuffer>` channel) can drive
+/// the same IEC 61850 timing state machine without going through that channel.
+pub(crate) fn advance_and_encode(
+    frame: &mut RetransmitFrame,
+    reset_by_new_data: bool,
+    now: Instant,
+    buffer_pool: &BufferPool,
+) -> Option<PooledBuffer> {
+    if reset_by_new_data {
+        frame.pdu.stNum = frame.pdu.stNum.wrapping_add(1);
+        frame.pdu.sqNum = 0;
+        frame.current_interval_ms = frame.profile.t_min_ms;
+        info!(
+            "New UDP data: APPID {} stNum incremented to {}, sqNum reset to 0, interval reset to {}ms",
+            u16::from_be_bytes(frame.header.APPID),
+            frame.pdu.stNum,
+            frame.current_interval_ms
+        );
+    } else {
+        frame.pdu.sqNum = frame.pdu.sqNum.wrapping_add(1);
+        info!(
+            "Timeout retransmit: APPID {} stNum {} sqNum {} (interval: {}ms)",
+            u16::from_be_bytes(frame.header.APPID),
+            frame.pdu.stNum,
+            frame.pdu.sqNum,
+            frame.current_interval_ms
+        );
+    }
+
+    frame.pdu.timeAllowedtoLive = frame.profile.time_allowed_to_live_ms(frame.current_interval_ms);
+
+    let encoded = encode_into_pooled_buffer(buffer_pool, &mut frame.header, &mut frame.pdu);
+    if encoded.is_none() {
+        error!(
+            "BufferPool exhausted, dropping retransmit frame for APPID {}",
+            u16::from_be_bytes(frame.header.APPID)
+        );
+    }
+
+    frame.next_due = now + Duration::from_millis(frame.current_interval_ms);
+    if !reset_by_new_data {
+        frame.current_interval_ms = frame.profile.next_interval_ms(frame.current_interval_ms);
+    }
+
+    encoded
+}
+
+/// [`advance_and_encode`] plus delivery to the GOOSE sender thread over
+/// `goose_tx`. This is the path `spawn_retransmit_thread` uses for frames that
+/// flow through the shared sender thread/channel.
+fn retransmit_frame(frame: &mut RetransmitFrame, reset_by_new_data: bool, now: Instant, buffer_pool: &BufferPool, goose_tx: &Sender<PooledBuffer>) {
+    if let Some(pooled) = advance_and_encode(frame, reset_by_new_data, now, buffer_pool) {
+        if let Err(e) = goose_tx.send(pooled) {
+            error!("Failed to send GOOSE frame to sender thread: {}", e);
+        }
+    }
+}
+
+/// Lock `frames`, recovering from a poisoned lock the same way the send loop does.
+fn lock_frames(frames: &RwLock<Vec<RetransmitFrame>>) -> std::sync::RwLockWriteGuard<'_, Vec<RetransmitFrame>> {
+    match frames.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            error!("⚠️  GOOSE frames lock was POISONED (another thread panicked), recovering");
+            poisoned.into_inner()
+        }
+    }
+}
+
+/// Inject a data-change event into one publisher's `allData`, identified by
+/// `goose_id` (`IECGoosePdu::goID`), and retransmit it immediately on the same
+/// "event burst" path `spawn_retransmit_thread` takes for a PLC-wide reset:
+/// `stNum` increments, `sqNum` resets to 0, and `current_interval_ms` restarts
+/// at `profile.t_min_ms`, from which it backs off on its own curve exactly as
+/// it would for a normal timeout-driven retransmit.
+///
+/// Unlike `RetransmitSignal` (which resets every frame in `frames` together),
+/// this only touches the one frame matching `goose_id`, so injecting a change
+/// into one publisher does not disturb another publisher's stNum/sqNum/timing.
+pub fn inject_data_change(
+    frames: &RwLock<Vec<RetransmitFrame>>,
+    buffer_pool: &BufferPool,
+    goose_tx: &Sender<PooledBuffer>,
+    goose_id: &str,
+    mutate: impl FnOnce(&mut Vec<IECData>),
+) -> Result<()> {
+    let mut frames_lock = lock_frames(frames);
+    let frame = frames_lock
+        .iter_mut()
+        .find(|frame| frame.pdu.goID == goose_id)
+        .ok_or_else(|| anyhow::anyhow!("no publisher frame with goID '{}'", goose_id))?;
+
+    mutate(&mut frame.pdu.allData);
+    retransmit_frame(frame, true, Instant::now(), buffer_pool, goose_tx);
+    Ok(())
+}
+
+/// Spawns the retransmission thread.
+///
+/// Each frame is driven on its own IEC 61850 retransmission curve
+/// (`RetransmissionProfile`): it is resent immediately whenever PLC data
+/// resets all frames (`reset_signal`), and otherwise independently once its
+/// own `current_interval_ms` elapses, growing that interval (and
+/// `timeAllowedToLive` alongside it) toward its profile's `t_max_ms` instead
+/// of every frame sharing one global backoff curve. Frames are encoded into a
+/// buffer leased from `buffer_pool`, so the hot path sends a pooled-buffer
+/// handle (returned to the pool on drop) over the channel instead of
+/// allocating a fresh `Vec<u8>` per frame.
+///
 /// # Arguments
-/// * `frames` - Shared GOOSE frames to transmit
+/// * `frames` - Shared GOOSE frames to transmit, each with its own profile/interval state
+/// * `buffer_pool` - Pool to lease encode buffers from (zero-allocation steady state)
 /// * `goose_tx` - Sender to GOOSE sender thread
 /// * `reset_signal` - High-precision Condvar signal for instant wakeup
 /// * `stop_signal` - Signal to stop the thread
-/// 
+///
 /// # Returns
 /// * `JoinHandle<()>` for the spawned thread
 pub fn spawn_retransmit_thread(
-    frames: Arc<RwLock<Vec<(EthernetHeader, IECGoosePdu)>>>,
-    goose_tx: Sender<Vec<u8>>,
+    frames: Arc<RwLock<Vec<RetransmitFrame>>>,
+    buffer_pool: Arc<BufferPool>,
+    goose_tx: Sender<PooledBuffer>,
     reset_signal: Arc<RetransmitSignal>,
     stop_signal: Arc<AtomicBool>,
 ) -> JoinHandle<()> {
     thread::spawn(move || {
-        const MAX_INTERVAL_MS: u64 = 5000;
-        const INITIAL_INTERVAL_MS: u64 = 2;
-        let mut current_interval_ms = INITIAL_INTERVAL_MS;
-
         // Wait for first PLC command before starting retransmissions
         // This prevents sending empty/zero frames on application startup
         info!("Retransmission thread waiting for first PLC command...");
@@ -45,18 +263,30 @@ pub fn spawn_retransmit_thread(
                 info!("Retransmission thread stopped before receiving first data");
                 return;
             }
-            
+
             // Wait for first data with 100ms timeout, then retry immediately
             // No sleep needed - wait_timeout() blocks efficiently with Condvar
             if reset_signal.wait_timeout(Duration::from_millis(100)) {
                 info!("First PLC command received, starting retransmission sequence");
-                break;  // Start retransmissions immediately (no delay!)
+                break; // Start retransmissions immediately (no delay!)
             }
             // Timeout: loop back instantly and wait again
+        }
 
+        // Every frame starts due immediately, so the first pass through the
+        // loop below sends all of them regardless of reset_by_new_data.
+        {
+            let mut frames_lock = lock_frames(&frames);
+            let now = Instant::now();
+            for frame in frames_lock.iter_mut() {
+                frame.current_interval_ms = frame.profile.t_min_ms;
+                frame.next_due = now;
+            }
         }
+
         // Flag to treat first iteration as new data arrival (signal was consumed in initial wait)
         let mut is_first_transmission = true;
+        let mut jitter = JitterCompensator::new();
 
         loop {
             if stop_signal.load(Ordering::Relaxed) {
@@ -64,24 +294,35 @@ pub fn spawn_retransmit_thread(
                 break;
             }
 
-            // High-precision wait with Condvar for instant wakeup
+            // Wait until the earliest frame is due, corrected by the jitter
+            // compensator's running estimate so the effective period converges
+            // on each frame's own curve despite per-wakeup scheduling latency.
             let loop_start = Instant::now();
-            let target_duration = Duration::from_millis(current_interval_ms);
-            let sleep_target_ms = current_interval_ms;
-            
+            let nominal_wait_ms = {
+                let frames_lock = lock_frames(&frames);
+                frames_lock
+                    .iter()
+                    .map(|frame| frame.next_due.saturating_duration_since(loop_start).as_millis() as u64)
+                    .min()
+                    .unwrap_or(DEFAULT_POLL_MS)
+                    .max(1)
+            };
+            let sleep_target_ms = corrected_target_ms(nominal_wait_ms, jitter.correction_ms());
+            let target_duration = Duration::from_millis(sleep_target_ms);
+
             // Wait with precise timing - Condvar provides instant wakeup on signal
             // Returns true ONLY when PLC sends new data via UDP (signal_reset called)
             // Returns false when timeout expires (regular retransmission)
             // OR treat first transmission after startup as new data
             let reset_by_new_data = reset_signal.wait_timeout(target_duration) || is_first_transmission;
-            
+
             let actual_elapsed = loop_start.elapsed();
-            
+
             // Check stop signal
             if stop_signal.load(Ordering::Relaxed) {
                 return;
             }
-            
+
             // Log timing info
             if reset_by_new_data {
                 if is_first_transmission {
@@ -90,110 +331,116 @@ pub fn spawn_retransmit_thread(
                     info!("New PLC data via UDP after {:?} (target was {:?}), instant wakeup",
                           actual_elapsed, target_duration);
                 }
-            } else if actual_elapsed.as_millis() as u64 > sleep_target_ms + 1 {
-                // Only warn if significantly over (>1ms, accounting for encoding time)
-                warn!(
-                    "⏱️  Timing variance: target {}ms, actual {:?} (+{}µs)",
-                    sleep_target_ms,
-                    actual_elapsed,
-                    actual_elapsed.as_micros() as i64 - (sleep_target_ms * 1000) as i64
-                );
+                // Every frame's interval restarts at its own t_min_ms next
+                // iteration, so the jitter history no longer applies.
+                jitter.reset();
+            } else {
+                jitter.record(actual_elapsed, target_duration);
+                if actual_elapsed.as_millis() as u64 > sleep_target_ms + 1 {
+                    // Only warn if significantly over (>1ms, accounting for encoding time)
+                    warn!(
+                        "⏱️  Timing variance: target {}ms, actual {:?} (+{}µs)",
+                        sleep_target_ms,
+                        actual_elapsed,
+                        actual_elapsed.as_micros() as i64 - (sleep_target_ms * 1000) as i64
+                    );
+                }
             }
-            
 
             // Clear first transmission flag after first iteration
             if is_first_transmission {
                 is_first_transmission = false;
             }
-            // Save whether we should double interval (before potential reset)
-            // Only continue exponential backoff when timeout (false), not when new UDP data (true)
-            let should_double_interval = !reset_by_new_data;
-            
+
             if reset_by_new_data {
-                current_interval_ms = INITIAL_INTERVAL_MS;
-                info!(
-                    "New data arrived from PLC - reset interval to {}ms, will increment stNum and reset sqNum",
-                    INITIAL_INTERVAL_MS
-                );
+                info!("New data arrived from PLC - resetting every frame's interval to its own t_min_ms");
             }
 
-            // Update sequence numbers and send GOOSE frames
+            // Retransmit whichever frames are due: all of them on a PLC reset,
+            // or just the ones whose own next_due has elapsed on a timeout.
             // SAFETY: Use poison recovery to handle panics in other threads
-            let result: Result<(), ()> = match frames.write() {
-                Ok(mut frames_lock) => {
-                    // Update sequence numbers and encode under lock
-                    // Lock hold time: ~200-500µs for 3 frames (acceptable)
-                    for frame in frames_lock.iter_mut() {
-                        if reset_by_new_data {
-                            frame.1.stNum = frame.1.stNum.wrapping_add(1);
-                            frame.1.sqNum = 0;
-                            // frame.1.timeAllowedtoLive = 
-                            info!(
-                                "New UDP data: APPID {} stNum incremented to {}, sqNum reset to 0",
-                                u16::from_be_bytes(frame.0.APPID),
-                                frame.1.stNum
-                            );
-                        } else {
-                            frame.1.sqNum = frame.1.sqNum.wrapping_add(1);
-                            info!(
-                                "Timeout retransmit: APPID {} stNum {} sqNum {} (interval: {}ms)",
-                                u16::from_be_bytes(frame.0.APPID),
-                                frame.1.stNum,
-                                frame.1.sqNum,
-                                current_interval_ms
-                            );
-                        }
-
-                        // Encode GOOSE frame while holding lock
-                        let mut buffer = [0u8; GOOSE_BUFFER_SIZE];
-                        let goose_frame_size = encodeGooseFrame(&mut frame.0, &frame.1, &mut buffer, 0);
-                        // info!(
-                        //     "Encoded GOOSE frame: APPID {} size {} bytes",
-                        //     u16::from_be_bytes(frame.0.APPID),
-                        //     goose_frame_size
-                        // );
-                        // Send to GOOSE sender thread via channel
-                        if let Err(e) = goose_tx.send(buffer[..goose_frame_size].to_vec()) {
-                            error!("Failed to send GOOSE frame to sender thread: {}", e);
-                        }
-                    }
-                    Ok(())
+            let now = Instant::now();
+            let mut frames_lock = lock_frames(&frames);
+            for frame in frames_lock.iter_mut() {
+                if reset_by_new_data || now >= frame.next_due {
+                    retransmit_frame(frame, reset_by_new_data, now, &buffer_pool, &goose_tx);
                 }
-                Err(poisoned) => {
-                    // POISON RECOVERY: Another thread panicked while holding lock
-                    // We can still access the data safely
-                    error!("⚠️  GOOSE frames lock was POISONED (another thread panicked)");
-                    error!("Attempting to recover and continue operation...");
-                    
-                    let mut frames_lock = poisoned.into_inner();
-                    // Still update sequence numbers and send frames
-                    for frame in frames_lock.iter_mut() {
-                        if reset_by_new_data {
-                            frame.1.stNum = frame.1.stNum.wrapping_add(1);
-                            frame.1.sqNum = 0;
-                        } else {
-                            frame.1.sqNum = frame.1.sqNum.wrapping_add(1);
-                        }
-
-                        let mut buffer = [0u8; GOOSE_BUFFER_SIZE];
-                        let goose_frame_size = encodeGooseFrame(&mut frame.0, &frame.1, &mut buffer, 0);
-                        if let Err(e) = goose_tx.send(buffer[..goose_frame_size].to_vec()) {
-                            error!("Failed to send GOOSE frame: {}", e);
-                        }
-                    }
-                    info!("✓ Successfully recovered from poisoned lock");
-                    Ok(())
-                }
-            };
-
-            if result.is_err() {
-                error!("Failed to process GOOSE frames, will retry next interval");
-            }
-
-            // Double interval for next iteration (but NOT when new data just arrived)
-            if should_double_interval && current_interval_ms < MAX_INTERVAL_MS {
-                current_interval_ms = (current_interval_ms * 2).min(MAX_INTERVAL_MS);
             }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+
+    fn sample_frame(goose_id: &str) -> RetransmitFrame {
+        let mut pdu = IECGoosePdu::default();
+        pdu.goID = goose_id.to_string();
+        pdu.allData = vec![IECData::boolean(false)];
+        RetransmitFrame::new(EthernetHeader::default(), pdu, RetransmissionProfile::default())
+    }
+
+    #[test]
+    fn test_inject_data_change_drives_one_publisher_stnum_sqnum() {
+        let frames = RwLock::new(vec![sample_frame("pub1"), sample_frame("pub2")]);
+        let pool = BufferPool::new(4);
+        let (tx, rx) = unbounded();
+
+        inject_data_change(&frames, &pool, &tx, "pub1", |all_data| {
+            all_data[0] = IECData::boolean(true);
+        })
+        .expect("pub1 exists");
+
+        let frames_lock = frames.read().unwrap();
+        let pub1 = frames_lock.iter().find(|f| f.pdu.goID == "pub1").unwrap();
+        let pub2 = frames_lock.iter().find(|f| f.pdu.goID == "pub2").unwrap();
+
+        assert_eq!(pub1.pdu.stNum, 1, "injected frame's stNum increments");
+        assert_eq!(pub1.pdu.sqNum, 0, "injected frame's sqNum resets to 0");
+        assert_eq!(pub1.current_interval_ms, pub1.profile.t_min_ms);
+        assert!(matches!(pub1.pdu.allData[0], IECData::boolean(true)));
+
+        assert_eq!(pub2.pdu.stNum, 0, "untargeted publisher is untouched");
+        assert_eq!(pub2.pdu.sqNum, 0);
+
+        assert!(rx.try_recv().is_ok(), "injection sends exactly one frame");
+        assert!(rx.try_recv().is_err(), "only the targeted frame is sent");
+    }
+
+    #[test]
+    fn test_inject_data_change_unknown_goose_id_errors() {
+        let frames = RwLock::new(vec![sample_frame("pub1")]);
+        let pool = BufferPool::new(4);
+        let (tx, _rx) = unbounded();
+
+        let result = inject_data_change(&frames, &pool, &tx, "does-not-exist", |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_retransmit_frame_timeout_backs_off_then_event_resets() {
+        let mut frame = sample_frame("pub1");
+        let pool = BufferPool::new(4);
+        let (tx, rx) = unbounded();
+        let now = Instant::now();
+
+        // Two timeout-driven retransmits grow the interval on the profile's curve.
+        retransmit_frame(&mut frame, false, now, &pool, &tx);
+        assert_eq!(frame.pdu.sqNum, 1);
+        assert_eq!(frame.current_interval_ms, frame.profile.t_min_ms * 2);
+
+        retransmit_frame(&mut frame, false, now, &pool, &tx);
+        assert_eq!(frame.pdu.sqNum, 2);
+        assert_eq!(frame.current_interval_ms, frame.profile.t_min_ms * 4);
+
+        // A data-change event resets stNum/sqNum/interval regardless of backoff progress.
+        retransmit_frame(&mut frame, true, now, &pool, &tx);
+        assert_eq!(frame.pdu.stNum, 1);
+        assert_eq!(frame.pdu.sqNum, 0);
+        assert_eq!(frame.current_interval_ms, frame.profile.t_min_ms);
+
+        assert_eq!(rx.try_iter().count(), 3);
+    }
+}
@@ -1,21 +1,64 @@
 use crate::pcs::MutablePcsData;
 use log::{info, warn};
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::io::Write;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 
+/// Latest validity snapshot, shared between this thread and
+/// `spawn_validity_telemetry_thread` so the telemetry socket thread never
+/// touches `MutablePcsData` (and can't contend with an RT worker for a
+/// DashMap entry lock) - it only ever locks this small `Mutex`.
+#[derive(Debug, Clone, Default)]
+pub struct ValiditySnapshot {
+    pub lan1_valid: usize,
+    pub lan1_invalid_ids: Vec<u16>,
+    pub lan2_valid: usize,
+    pub lan2_invalid_ids: Vec<u16>,
+}
+
+impl ValiditySnapshot {
+    /// Render as the newline-delimited `key=value` text protocol served by
+    /// `spawn_validity_telemetry_thread`, e.g.
+    /// `lan1_valid=12 lan1_invalid=2 lan1_invalid_ids=3,7`.
+    fn to_text(&self) -> String {
+        format!(
+            "lan1_valid={} lan1_invalid={} lan1_invalid_ids={}\nlan2_valid={} lan2_invalid={} lan2_invalid_ids={}\n",
+            self.lan1_valid,
+            self.lan1_invalid_ids.len(),
+            join_ids(&self.lan1_invalid_ids),
+            self.lan2_valid,
+            self.lan2_invalid_ids.len(),
+            join_ids(&self.lan2_invalid_ids),
+        )
+    }
+}
+
+fn join_ids(ids: &[u16]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")
+}
+
 /// Spawns the validity checking thread that periodically checks PCS validity
-/// 
+///
 /// # Arguments
 /// * `mutable_data` - Shared mutable PCS data (DashMap provides internal concurrency)
 /// * `validity_interval_ms` - Interval in milliseconds between validity checks
-/// 
+/// * `snapshot` - Latest validity snapshot, refreshed each cycle for `spawn_validity_telemetry_thread` to serve
+///
 /// # Returns
 /// * `JoinHandle` for the spawned thread
 pub fn spawn_validity_thread(
     mutable_data: Arc<MutablePcsData>,
     validity_interval_ms: u64,
+    snapshot: Arc<Mutex<ValiditySnapshot>>,
 ) -> JoinHandle<()> {
     thread::spawn(move || {
+        // check_validity_both_lans only reports *transitions* since the last
+        // call, so the running set of currently-invalid IDs is tracked here
+        // and published into `snapshot` after every check.
+        let mut lan1_invalid_ids: HashSet<u16> = HashSet::new();
+        let mut lan2_invalid_ids: HashSet<u16> = HashSet::new();
         info!(
             "Validity checking thread started with interval: {} ms",
             validity_interval_ms
@@ -59,15 +102,84 @@ pub fn spawn_validity_thread(
                 );
             }
             
+            // Fold this cycle's transitions into the running invalid-ID sets
+            lan1_invalid_ids.extend(lan1_invalid.iter().copied());
+            for id in &lan1_valid {
+                lan1_invalid_ids.remove(id);
+            }
+            lan2_invalid_ids.extend(lan2_invalid.iter().copied());
+            for id in &lan2_valid {
+                lan2_invalid_ids.remove(id);
+            }
+
             // Get overall validity statistics for monitoring
-            let ((lan1_valid_count, lan1_invalid_count, lan1_total), 
+            let ((lan1_valid_count, lan1_invalid_count, lan1_total),
                  (lan2_valid_count, lan2_invalid_count, lan2_total)) = mutable_data.get_validity_stats_both_lans();
-            
+
             info!(
                 "Validity check complete - LAN1: {}/{} valid ({} invalid), LAN2: {}/{} valid ({} invalid)",
                 lan1_valid_count, lan1_total, lan1_invalid_count,
                 lan2_valid_count, lan2_total, lan2_invalid_count
             );
+
+            // Publish the snapshot the telemetry thread serves over TCP
+            let mut lan1_ids: Vec<u16> = lan1_invalid_ids.iter().copied().collect();
+            lan1_ids.sort_unstable();
+            let mut lan2_ids: Vec<u16> = lan2_invalid_ids.iter().copied().collect();
+            lan2_ids.sort_unstable();
+            *snapshot.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = ValiditySnapshot {
+                lan1_valid: lan1_valid_count,
+                lan1_invalid_ids: lan1_ids,
+                lan2_valid: lan2_valid_count,
+                lan2_invalid_ids: lan2_ids,
+            };
+        }
+    })
+}
+
+/// Spawns a TCP telemetry server that serves the latest `ValiditySnapshot` as
+/// a newline-delimited `key=value` text protocol on each connection (e.g.
+/// `lan1_valid=12 lan1_invalid=2 lan1_invalid_ids=3,7`), following the simple
+/// line-based scrape-service pattern used by embedded RT network stacks. Only
+/// ever locks the small snapshot `Mutex`, so a slow or stalled scraper can't
+/// back-pressure the validity-checking thread.
+///
+/// # Arguments
+/// * `snapshot` - Shared validity snapshot, refreshed by `spawn_validity_thread`
+/// * `port` - TCP port to listen on (bound on all interfaces)
+///
+/// # Returns
+/// * `JoinHandle` for the spawned thread
+pub fn spawn_validity_telemetry_thread(
+    snapshot: Arc<Mutex<ValiditySnapshot>>,
+    port: u16,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let listener = match TcpListener::bind(("0.0.0.0", port)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("Validity telemetry: failed to bind TCP port {}: {}", port, e);
+                return;
+            }
+        };
+        info!("Validity telemetry: serving scrape text on port {}", port);
+
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("Validity telemetry: failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+
+            let text = snapshot
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .to_text();
+            if let Err(e) = stream.write_all(text.as_bytes()) {
+                warn!("Validity telemetry: failed to write to client: {}", e);
+            }
         }
     })
 }